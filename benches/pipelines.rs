@@ -0,0 +1,117 @@
+// Composed pipelines against the hand-written code they replace, calling the same stage
+// functions. Every combinator is generic, so the composed closures are monomorphized in
+// the calling crate and should compile to the same code as the baselines.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use rust_overture::chain::chain3_throwing;
+use rust_overture::compose::{compose3, compose3_res};
+use rust_overture::func::Func;
+use rust_overture::options::zip3;
+use rust_overture::pipe::Pipe;
+
+#[derive(Clone, Copy)]
+struct Transaction {
+    amount: f64,
+    country: u8,
+    hour: u8,
+}
+
+fn transactions() -> Vec<Transaction> {
+    (0..10_000u32).map(|i| Transaction { amount: f64::from(i % 997) * 3.5, country: (i % 7) as u8, hour: (i % 24) as u8 }).collect()
+}
+
+fn amount_score(t: Transaction) -> (Transaction, u32) {
+    (t, if t.amount > 3000.0 { 40 } else { 0 })
+}
+
+fn country_score((t, s): (Transaction, u32)) -> (Transaction, u32) {
+    (t, s + if t.country == 3 { 30 } else { 0 })
+}
+
+fn night_score((t, s): (Transaction, u32)) -> u32 {
+    s + if t.hour < 6 { 20 } else { 0 }
+}
+
+fn hand_written(t: Transaction) -> u32 {
+    let mut score = 0;
+    if t.amount > 3000.0 {
+        score += 40;
+    }
+    if t.country == 3 {
+        score += 30;
+    }
+    if t.hour < 6 {
+        score += 20;
+    }
+    score
+}
+
+fn checked_amount(t: Transaction) -> Result<Transaction, &'static str> {
+    if t.amount >= 0.0 { Ok(t) } else { Err("negative amount") }
+}
+
+fn checked_country(t: Transaction) -> Result<Transaction, &'static str> {
+    if t.country < 200 { Ok(t) } else { Err("unknown country") }
+}
+
+fn checked_score(t: Transaction) -> Result<u32, &'static str> {
+    Ok(hand_written(t))
+}
+
+fn hand_written_checked(t: Transaction) -> Result<u32, &'static str> {
+    let t = checked_amount(t)?;
+    let t = checked_country(t)?;
+    checked_score(t)
+}
+
+fn scoring(c: &mut Criterion) {
+    let txs = transactions();
+    let mut group = c.benchmark_group("fraud score");
+    group.bench_function("nested calls", |b| {
+        b.iter(|| black_box(&txs).iter().map(|&t| night_score(country_score(amount_score(t)))).sum::<u32>())
+    });
+    let composed = compose3(night_score, country_score, amount_score);
+    group.bench_function("compose3", |b| b.iter(|| black_box(&txs).iter().map(|&t| composed(t)).sum::<u32>()));
+    group.bench_function("pipe", |b| {
+        b.iter(|| black_box(&txs).iter().map(|&t| t.pipe(amount_score).pipe(country_score).pipe(night_score)).sum::<u32>())
+    });
+    let operators = Func::new(amount_score) >> Func::new(country_score) >> Func::new(night_score);
+    group.bench_function("Func >>", |b| b.iter(|| black_box(&txs).iter().map(|&t| operators.call(t)).sum::<u32>()));
+    group.finish();
+}
+
+fn throwing(c: &mut Criterion) {
+    let txs = transactions();
+    let mut group = c.benchmark_group("checked fraud score");
+    group.bench_function("hand-written", |b| b.iter(|| black_box(&txs).iter().map(|&t| hand_written_checked(t)).sum::<Result<u32, &str>>()));
+    let composed = compose3_res(checked_score, checked_country, checked_amount);
+    group.bench_function("compose3_res", |b| b.iter(|| black_box(&txs).iter().map(|&t| composed(t)).sum::<Result<u32, &str>>()));
+    let chained = chain3_throwing(checked_amount, checked_country, checked_score);
+    group.bench_function("chain3_throwing", |b| b.iter(|| black_box(&txs).iter().map(|&t| chained(t)).sum::<Result<u32, &str>>()));
+    group.finish();
+}
+
+fn zipping(c: &mut Criterion) {
+    let values: Vec<(Option<u32>, Option<u32>, Option<u32>)> = (0..10_000).map(|i| (Some(i), (i % 11 != 0).then_some(i), Some(1))).collect();
+    let mut group = c.benchmark_group("zip3");
+    group.bench_function("match", |b| {
+        b.iter(|| {
+            black_box(&values)
+                .iter()
+                .filter_map(|&(a, b, c)| match (a, b, c) {
+                    (Some(a), Some(b), Some(c)) => Some(a + b + c),
+                    _ => None,
+                })
+                .sum::<u32>()
+        })
+    });
+    group.bench_function("options::zip3", |b| {
+        b.iter(|| black_box(&values).iter().filter_map(|&(a, b, c)| zip3(a, b, c).map(|(a, b, c)| a + b + c)).sum::<u32>())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, scoring, throwing, zipping);
+criterion_main!(benches);
@@ -2,7 +2,8 @@ use rust_overture::{concat::*, concat_fn, concat_mut, concat_tryfn, concat_trymu
 
 #[derive(Debug)]
 enum Error {
-    some_error
+    #[allow(dead_code)]
+    SomeError
 }
 fn main() {
     // --- concat_fn! ---
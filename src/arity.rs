@@ -0,0 +1,22 @@
+// `arities!` generates the fixed-arity families (zip2..zip16 and friends) from one
+// macro, so every module supports the same arities with the same parameter names.
+//
+// `arities!(zip_option; _, zip2, zip3)` expands to
+// `zip_option!(zip2, a: A, b: B); zip_option!(zip3, a: A, b: B, c: C);`: the Nth name
+// gets N parameters, and `_` skips an arity. The letters leave out `F`, `N` and `R`,
+// which the generating macros use for closures, array lengths and results.
+
+macro_rules! arities {
+    ($mac:ident; $($name:tt),+ $(,)?) => {
+        arities!(@step $mac; [$($name),+]; [];
+            [a: A, b: B, c: C, d: D, e: E, g: G, h: H, i: I, j: J, k: K, l: L, m: M, o: O, p: P, q: Q, s: S]);
+    };
+    (@step $mac:ident; []; [$($done:tt)*]; [$($left:tt)*]) => {};
+    (@step $mac:ident; [_ $(, $rest:tt)*]; [$($done:tt)*]; [$a:ident: $A:ident $(, $b:ident: $B:ident)*]) => {
+        arities!(@step $mac; [$($rest),*]; [$($done)* $a: $A,]; [$($b: $B),*]);
+    };
+    (@step $mac:ident; [$name:ident $(, $rest:tt)*]; [$($done:tt)*]; [$a:ident: $A:ident $(, $b:ident: $B:ident)*]) => {
+        $mac!($name, $($done)* $a: $A);
+        arities!(@step $mac; [$($rest),*]; [$($done)* $a: $A,]; [$($b: $B),*]);
+    };
+}
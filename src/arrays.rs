@@ -0,0 +1,52 @@
+// Zips over fixed-size arrays. The length `N` is part of the type, so the inputs can't
+// differ in length and the result is built in place, without a `Vec`.
+
+macro_rules! zip_arrays {
+    ($name:ident, $($a:ident: $A:ident),+) => {
+        #[allow(clippy::too_many_arguments)]
+        pub fn $name<$($A,)+ const N: usize>($($a: [$A; N]),+) -> [($($A,)+); N] {
+            $(let mut $a = $a.into_iter();)+
+            core::array::from_fn(|_| ($($a.next().expect("all arrays have length N"),)+))
+        }
+    };
+}
+
+arities!(zip_arrays; _, zip2, zip3, zip4, zip5, zip6, zip7, zip8, zip9, zip10, zip11, zip12, zip13, zip14, zip15, zip16);
+
+/// Zip two arrays and combine each pair with `f`.
+pub fn zip2_with<A, B, R, F, const N: usize>(a: [A; N], b: [B; N], mut f: F) -> [R; N]
+where
+    F: FnMut(A, B) -> R,
+{
+    zip2(a, b).map(|(a, b)| f(a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zip2() {
+        assert_eq!(zip2([1, 2, 3], ['a', 'b', 'c']), [(1, 'a'), (2, 'b'), (3, 'c')]);
+        assert_eq!(zip2::<i32, i32, 0>([], []), []);
+    }
+
+    #[test]
+    fn test_zip2_with_dot_product() {
+        let products = zip2_with([1.0, 2.0, 3.0], [4.0, 5.0, 6.0], |x, y| x * y);
+        assert_eq!(products.iter().sum::<f64>(), 32.0);
+    }
+
+    #[test]
+    fn test_zip6_moves_non_copy_values() {
+        let s = |x: &str| x.to_string();
+        let zipped = zip6([s("a")], [1], [true], [s("b")], [2.5], ['c']);
+        assert_eq!(zipped, [(s("a"), 1, true, s("b"), 2.5, 'c')]);
+    }
+
+    #[test]
+    fn test_zip16() {
+        let z = zip16([0], [1], [2], [3], [4], [5], [6], [7], [8], [9], [10], [11], [12], [13], [14], ['p']);
+        assert_eq!(z[0].15, 'p');
+    }
+}
@@ -0,0 +1,169 @@
+// Arrow-style wiring for tuple-shaped pipelines: feed one input to several functions,
+// or run functions side by side on the parts of a tuple.
+
+/// Feed one input to both `f` and `g` and pair the results.
+pub fn fanout<A, B, C, F, G>(f: F, g: G) -> impl Fn(A) -> (B, C)
+where
+    A: Clone,
+    F: Fn(A) -> B,
+    G: Fn(A) -> C,
+{
+    move |a: A| (f(a.clone()), g(a))
+}
+
+// juxtN: like `fanout`, for N functions. The input is cloned for all but the last.
+macro_rules! juxt {
+    ($name:ident, $($f:ident: $F:ident -> $R:ident),+; $last:ident: $L:ident -> $LR:ident) => {
+        pub fn $name<A, $($R,)+ $LR, $($F,)+ $L>($($f: $F,)+ $last: $L) -> impl Fn(A) -> ($($R,)+ $LR)
+        where
+            A: Clone,
+            $($F: Fn(A) -> $R,)+
+            $L: Fn(A) -> $LR,
+        {
+            move |a: A| ($($f(a.clone()),)+ $last(a))
+        }
+    };
+}
+
+juxt!(juxt3, f1: F1 -> B1, f2: F2 -> B2; f3: F3 -> B3);
+juxt!(juxt4, f1: F1 -> B1, f2: F2 -> B2, f3: F3 -> B3; f4: F4 -> B4);
+juxt!(juxt5, f1: F1 -> B1, f2: F2 -> B2, f3: F3 -> B3, f4: F4 -> B4; f5: F5 -> B5);
+juxt!(juxt6, f1: F1 -> B1, f2: F2 -> B2, f3: F3 -> B3, f4: F4 -> B4, f5: F5 -> B5; f6: F6 -> B6);
+
+/// Run `f` on the first element of a pair and `g` on the second.
+pub fn split<A, B, C, D, F, G>(f: F, g: G) -> impl Fn((A, B)) -> (C, D)
+where
+    F: Fn(A) -> C,
+    G: Fn(B) -> D,
+{
+    move |(a, b): (A, B)| (f(a), g(b))
+}
+
+/// Transform the first element of a pair, carrying the second along untouched.
+pub fn first<A, B, C, F>(f: F) -> impl Fn((A, C)) -> (B, C)
+where
+    F: Fn(A) -> B,
+{
+    move |(a, c): (A, C)| (f(a), c)
+}
+
+/// Transform the second element of a pair, carrying the first along untouched.
+pub fn second<A, B, C, F>(f: F) -> impl Fn((C, A)) -> (C, B)
+where
+    F: Fn(A) -> B,
+{
+    move |(c, a): (C, A)| (c, f(a))
+}
+
+/// Map both sides of a pair: the same wiring as [`split`], under its usual name for
+/// two-sided containers (see also `result::bimap`).
+pub fn bimap<A, B, C, D, F, G>(f: F, g: G) -> impl Fn((A, B)) -> (C, D)
+where
+    F: Fn(A) -> C,
+    G: Fn(B) -> D,
+{
+    split(f, g)
+}
+
+// --- Throwing versions (Swift `throws` → Rust `Result`)
+
+/// [`split`] for throwing functions; `g` is not run if `f` fails.
+pub fn split_throwing<A, B, C, D, E, F, G>(f: F, g: G) -> impl Fn((A, B)) -> Result<(C, D), E>
+where
+    F: Fn(A) -> Result<C, E>,
+    G: Fn(B) -> Result<D, E>,
+{
+    move |(a, b): (A, B)| Ok((f(a)?, g(b)?))
+}
+
+/// [`fanout`] for throwing functions; `g` is not run if `f` fails.
+pub fn fanout_throwing<A, B, C, E, F, G>(f: F, g: G) -> impl Fn(A) -> Result<(B, C), E>
+where
+    A: Clone,
+    F: Fn(A) -> Result<B, E>,
+    G: Fn(A) -> Result<C, E>,
+{
+    move |a: A| Ok((f(a.clone())?, g(a)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct Transaction {
+        amount: f64,
+        country: &'static str,
+        hour: u8,
+        new_device: bool,
+    }
+
+    #[test]
+    fn test_fanout() {
+        let stats = fanout(|v: Vec<i32>| v.iter().sum::<i32>(), |v: Vec<i32>| v.len());
+        assert_eq!(stats(vec![1, 2, 3]), (6, 3));
+    }
+
+    #[test]
+    fn test_juxt4_fraud_risks() {
+        let risks = juxt4(
+            |t: Transaction| if t.amount > 1000.0 { 0.4 } else { 0.0 },
+            |t: Transaction| if t.country != "NL" { 0.2 } else { 0.0 },
+            |t: Transaction| if t.hour < 6 { 0.3 } else { 0.0 },
+            |t: Transaction| if t.new_device { 0.1 } else { 0.0 },
+        );
+        let t = Transaction { amount: 5000.0, country: "NL", hour: 3, new_device: true };
+        assert_eq!(risks(t), (0.4, 0.0, 0.3, 0.1));
+    }
+
+    #[test]
+    fn test_juxt6() {
+        let f = juxt6(|n: i32| n, |n| n + 1, |n| n * 2, |n| n - 1, |n| n.to_string(), |n| n > 0);
+        assert_eq!(f(3), (3, 4, 6, 2, "3".to_string(), true));
+    }
+
+    #[test]
+    fn test_split() {
+        let f = split(|s: &str| s.len(), |n: i32| n * 2);
+        assert_eq!(f(("abc", 5)), (3, 10));
+    }
+
+    #[test]
+    fn test_split_throwing() {
+        let parse_pair = split_throwing(|s: &str| s.parse::<i32>(), |s: &str| s.parse::<i32>());
+        assert_eq!(parse_pair(("1", "2")), Ok((1, 2)));
+        assert!(parse_pair(("1", "x")).is_err());
+    }
+
+    #[test]
+    fn test_fanout_then_split() {
+        let check = fanout_throwing(
+            |s: String| s.parse::<u32>().map_err(|_| "not a number"),
+            |s: String| if s.len() < 4 { Ok(s) } else { Err("too long") },
+        );
+        let describe = split(|n: u32| n + 1, |s: String| s.len());
+        assert_eq!(check("42".to_string()).map(describe), Ok((43, 2)));
+        assert_eq!(check("12345".to_string()), Err("too long"));
+    }
+
+    #[test]
+    fn test_first_and_second_carry_context() {
+        let normalize = second(|amount: f64| (amount * 100.0).round() / 100.0);
+        assert_eq!(normalize(("tx-1", 12.345)), ("tx-1", 12.35));
+        let upper = first(|s: &str| s.to_uppercase());
+        assert_eq!(upper(("eur", 7)), ("EUR".to_string(), 7));
+    }
+
+    #[test]
+    fn test_first_in_pipeline() {
+        let ids_and_raw = vec![(" 10 ", 1), ("20", 2)];
+        let parsed: Vec<(i32, i32)> = ids_and_raw.into_iter().map(first(|s: &str| s.trim().parse().unwrap())).collect();
+        assert_eq!(parsed, vec![(10, 1), (20, 2)]);
+    }
+
+    #[test]
+    fn test_bimap_pair() {
+        let f = bimap(|n: i32| n.to_string(), |b: bool| !b);
+        assert_eq!(f((1, true)), ("1".to_string(), false));
+    }
+}
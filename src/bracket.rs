@@ -0,0 +1,122 @@
+// Resource-safety combinators for throwing pipeline stages.
+
+/// Runs its closure when dropped, i.e. on return and on unwinding.
+struct Finally<C: Fn()>(C);
+
+impl<C: Fn()> Drop for Finally<C> {
+    fn drop(&mut self) {
+        (self.0)()
+    }
+}
+
+/// Run `cleanup` after every call of `f`, whether it succeeded, failed or panicked,
+/// and return `f`'s original result: like Swift `defer` / a `finally` block.
+pub fn ensuring<A, B, E, F, C>(f: F, cleanup: C) -> impl Fn(A) -> Result<B, E>
+where
+    F: Fn(A) -> Result<B, E>,
+    C: Fn(),
+{
+    move |a: A| {
+        let _finally = Finally(&cleanup);
+        f(a)
+    }
+}
+
+/// Releases the resource it holds when dropped, i.e. on return and on unwinding.
+struct Release<'a, R, L: Fn(R)> {
+    resource: Option<R>,
+    release: &'a L,
+}
+
+impl<R, L: Fn(R)> Drop for Release<'_, R, L> {
+    fn drop(&mut self) {
+        if let Some(resource) = self.resource.take() {
+            (self.release)(resource)
+        }
+    }
+}
+
+/// Acquire a resource from the input, use it, and always release it afterwards,
+/// whether `use_resource` succeeded, failed or panicked. Nothing is released when
+/// `acquire` itself fails.
+pub fn bracket<A, R, B, E, Acq, U, L>(acquire: Acq, use_resource: U, release: L) -> impl Fn(A) -> Result<B, E>
+where
+    Acq: Fn(A) -> Result<R, E>,
+    U: Fn(&mut R) -> Result<B, E>,
+    L: Fn(R),
+{
+    move |a: A| {
+        let mut guard = Release { resource: Some(acquire(a)?), release: &release };
+        let resource = guard.resource.as_mut().expect("resource is held until the guard drops");
+        use_resource(resource)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_ensuring_runs_on_success_and_failure() {
+        let cleanups = Cell::new(0);
+        let f = ensuring(
+            |x: i32| if x > 0 { Ok(x) } else { Err("not positive") },
+            || cleanups.set(cleanups.get() + 1),
+        );
+        assert_eq!(f(1), Ok(1));
+        assert_eq!(f(-1), Err("not positive"));
+        assert_eq!(cleanups.get(), 2);
+    }
+
+    #[test]
+    fn test_ensuring_runs_on_panic() {
+        let cleaned_up = std::sync::atomic::AtomicBool::new(false);
+        let f = ensuring(
+            |_: ()| -> Result<(), ()> { panic!("stage blew up") },
+            || cleaned_up.store(true, std::sync::atomic::Ordering::SeqCst),
+        );
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(())));
+        assert!(outcome.is_err());
+        assert!(cleaned_up.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[derive(Debug)]
+    struct Connection {
+        id: u32,
+        queries: Vec<String>,
+    }
+
+    #[test]
+    fn test_bracket_releases_after_success_and_failure() {
+        let released = std::cell::RefCell::new(Vec::new());
+        let query = bracket(
+            |id: u32| if id > 0 { Ok(Connection { id, queries: vec![] }) } else { Err("no such db".to_string()) },
+            |conn: &mut Connection| {
+                conn.queries.push("SELECT 1".to_string());
+                if conn.id == 2 { Err("query failed".to_string()) } else { Ok(conn.queries.len()) }
+            },
+            |conn: Connection| released.borrow_mut().push((conn.id, conn.queries.len())),
+        );
+
+        assert_eq!(query(1), Ok(1));
+        assert_eq!(query(2), Err("query failed".to_string()));
+        assert_eq!(*released.borrow(), vec![(1, 1), (2, 1)]);
+
+        assert_eq!(query(0), Err("no such db".to_string()));
+        assert_eq!(released.borrow().len(), 2, "nothing to release when acquire fails");
+    }
+
+    #[test]
+    fn test_bracket_releases_on_panic() {
+        let released = std::sync::atomic::AtomicBool::new(false);
+        let f = bracket(
+            |_: ()| Ok::<_, ()>(42),
+            |_: &mut i32| -> Result<(), ()> { panic!("use blew up") },
+            |_: i32| released.store(true, std::sync::atomic::Ordering::SeqCst),
+        );
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(())));
+        assert!(outcome.is_err());
+        assert!(released.load(std::sync::atomic::Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,41 @@
+/// The enum counterpart of a `KeyPath`: instead of getting and setting a field, it
+/// extracts a variant's payload (if the value is that variant) and embeds a payload
+/// back into the enum.
+pub struct CasePath<Root, Value> {
+    pub extract: fn(Root) -> Option<Value>,
+    pub embed: fn(Value) -> Root,
+}
+
+// Two fn pointers, so always `Copy`, like `Lens`.
+impl<Root, Value> Clone for CasePath<Root, Value> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Root, Value> Copy for CasePath<Root, Value> {}
+
+impl<Root, Value> CasePath<Root, Value> {
+    pub fn new(extract: fn(Root) -> Option<Value>, embed: fn(Value) -> Root) -> Self {
+        Self { extract, embed }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Click(u32, u32),
+        Key(char),
+    }
+
+    #[test]
+    fn test_extract_and_embed() {
+        let key = CasePath::new(|e| if let Event::Key(c) = e { Some(c) } else { None }, Event::Key);
+        assert_eq!((key.extract)(Event::Key('a')), Some('a'));
+        assert_eq!((key.extract)(Event::Click(1, 2)), None);
+        assert_eq!((key.embed)('b'), Event::Key('b'));
+    }
+}
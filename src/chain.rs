@@ -1,3 +1,5 @@
+use crate::prelude::*;
+
 /// Variadic-style macro for chaining functions that return Option, Result, or Vec.
 #[macro_export]
 macro_rules! chain {
@@ -17,7 +19,7 @@ pub fn chain_opt<A, B, C>(
     f: impl Fn(A) -> Option<B>,
     g: impl Fn(B) -> Option<C>,
 ) -> impl Fn(A) -> Option<C> {
-    move |a| f(a).and_then(|b| g(b))
+    move |a| f(a).and_then(&g)
 }
 
 // Result version (like Swift's throws -> Optional)
@@ -25,7 +27,7 @@ pub fn chain_result<A, B, C, E>(
     f: impl Fn(A) -> Result<B, E>,
     g: impl Fn(B) -> Result<C, E>,
 ) -> impl Fn(A) -> Result<C, E> {
-    move |a| f(a).and_then(|b| g(b))
+    move |a| f(a).and_then(&g)
 }
 
 // Vec version (like Swift's arrays)
@@ -33,9 +35,212 @@ pub fn chain_vec<A, B, C>(
     f: impl Fn(A) -> Vec<B>,
     g: impl Fn(B) -> Vec<C>,
 ) -> impl Fn(A) -> Vec<C> {
-    move |a| f(a).into_iter().flat_map(|b| g(b)).collect()
+    move |a| f(a).into_iter().flat_map(&g).collect()
+}
+
+
+// ---------------------------------------------------
+// Kleisli composition for Option (Swift `chain`)
+// chain3(f, g, h)(a) == f(a).and_then(g).and_then(h)
+// ---------------------------------------------------
+
+pub fn chain2<A, B, C, F1, F2>(f: F1, g: F2) -> impl Fn(A) -> Option<C>
+where
+    F1: Fn(A) -> Option<B>,
+    F2: Fn(B) -> Option<C>,
+{
+    move |a: A| f(a).and_then(&g)
+}
+
+pub fn chain3<A, B, C, D, F1, F2, F3>(f: F1, g: F2, h: F3) -> impl Fn(A) -> Option<D>
+where
+    F1: Fn(A) -> Option<B>,
+    F2: Fn(B) -> Option<C>,
+    F3: Fn(C) -> Option<D>,
+{
+    move |a: A| f(a).and_then(&g).and_then(&h)
+}
+
+pub fn chain4<A, B, C, D, E, F1, F2, F3, F4>(
+    f: F1,
+    g: F2,
+    h: F3,
+    i: F4,
+) -> impl Fn(A) -> Option<E>
+where
+    F1: Fn(A) -> Option<B>,
+    F2: Fn(B) -> Option<C>,
+    F3: Fn(C) -> Option<D>,
+    F4: Fn(D) -> Option<E>,
+{
+    move |a: A| f(a).and_then(&g).and_then(&h).and_then(&i)
+}
+
+pub fn chain5<A, B, C, D, E, G, F1, F2, F3, F4, F5>(
+    f: F1,
+    g: F2,
+    h: F3,
+    i: F4,
+    j: F5,
+) -> impl Fn(A) -> Option<G>
+where
+    F1: Fn(A) -> Option<B>,
+    F2: Fn(B) -> Option<C>,
+    F3: Fn(C) -> Option<D>,
+    F4: Fn(D) -> Option<E>,
+    F5: Fn(E) -> Option<G>,
+{
+    move |a: A| f(a).and_then(&g).and_then(&h).and_then(&i).and_then(&j)
+}
+
+pub fn chain6<A, B, C, D, E, G, H, F1, F2, F3, F4, F5, F6>(
+    f: F1,
+    g: F2,
+    h: F3,
+    i: F4,
+    j: F5,
+    k: F6,
+) -> impl Fn(A) -> Option<H>
+where
+    F1: Fn(A) -> Option<B>,
+    F2: Fn(B) -> Option<C>,
+    F3: Fn(C) -> Option<D>,
+    F4: Fn(D) -> Option<E>,
+    F5: Fn(E) -> Option<G>,
+    F6: Fn(G) -> Option<H>,
+{
+    move |a: A| {
+        f(a).and_then(&g)
+            .and_then(&h)
+            .and_then(&i)
+            .and_then(&j)
+            .and_then(&k)
+    }
+}
+
+// ---------------------------------------------------
+// Throwing versions (Swift `throws` → Rust `Result`)
+// Each stage may fail with its own error type, as long as it converts
+// `Into` the error type of the whole chain.
+// ---------------------------------------------------
+
+pub fn chain2_throwing<A, B, C, E, E1, E2, F1, F2>(f: F1, g: F2) -> impl Fn(A) -> Result<C, E>
+where
+    F1: Fn(A) -> Result<B, E1>,
+    F2: Fn(B) -> Result<C, E2>,
+    E1: Into<E>,
+    E2: Into<E>,
+{
+    move |a: A| {
+        let b = f(a).map_err(Into::into)?;
+        g(b).map_err(Into::into)
+    }
 }
 
+pub fn chain3_throwing<A, B, C, D, E, E1, E2, E3, F1, F2, F3>(
+    f: F1,
+    g: F2,
+    h: F3,
+) -> impl Fn(A) -> Result<D, E>
+where
+    F1: Fn(A) -> Result<B, E1>,
+    F2: Fn(B) -> Result<C, E2>,
+    F3: Fn(C) -> Result<D, E3>,
+    E1: Into<E>,
+    E2: Into<E>,
+    E3: Into<E>,
+{
+    move |a: A| {
+        let b = f(a).map_err(Into::into)?;
+        let c = g(b).map_err(Into::into)?;
+        h(c).map_err(Into::into)
+    }
+}
+
+pub fn chain4_throwing<A, B, C, D, G, E, E1, E2, E3, E4, F1, F2, F3, F4>(
+    f: F1,
+    g: F2,
+    h: F3,
+    i: F4,
+) -> impl Fn(A) -> Result<G, E>
+where
+    F1: Fn(A) -> Result<B, E1>,
+    F2: Fn(B) -> Result<C, E2>,
+    F3: Fn(C) -> Result<D, E3>,
+    F4: Fn(D) -> Result<G, E4>,
+    E1: Into<E>,
+    E2: Into<E>,
+    E3: Into<E>,
+    E4: Into<E>,
+{
+    move |a: A| {
+        let b = f(a).map_err(Into::into)?;
+        let c = g(b).map_err(Into::into)?;
+        let d = h(c).map_err(Into::into)?;
+        i(d).map_err(Into::into)
+    }
+}
+
+pub fn chain5_throwing<A, B, C, D, G, H, E, E1, E2, E3, E4, E5, F1, F2, F3, F4, F5>(
+    f: F1,
+    g: F2,
+    h: F3,
+    i: F4,
+    j: F5,
+) -> impl Fn(A) -> Result<H, E>
+where
+    F1: Fn(A) -> Result<B, E1>,
+    F2: Fn(B) -> Result<C, E2>,
+    F3: Fn(C) -> Result<D, E3>,
+    F4: Fn(D) -> Result<G, E4>,
+    F5: Fn(G) -> Result<H, E5>,
+    E1: Into<E>,
+    E2: Into<E>,
+    E3: Into<E>,
+    E4: Into<E>,
+    E5: Into<E>,
+{
+    move |a: A| {
+        let b = f(a).map_err(Into::into)?;
+        let c = g(b).map_err(Into::into)?;
+        let d = h(c).map_err(Into::into)?;
+        let e = i(d).map_err(Into::into)?;
+        j(e).map_err(Into::into)
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn chain6_throwing<A, B, C, D, G, H, I, E, E1, E2, E3, E4, E5, E6, F1, F2, F3, F4, F5, F6>(
+    f: F1,
+    g: F2,
+    h: F3,
+    i: F4,
+    j: F5,
+    k: F6,
+) -> impl Fn(A) -> Result<I, E>
+where
+    F1: Fn(A) -> Result<B, E1>,
+    F2: Fn(B) -> Result<C, E2>,
+    F3: Fn(C) -> Result<D, E3>,
+    F4: Fn(D) -> Result<G, E4>,
+    F5: Fn(G) -> Result<H, E5>,
+    F6: Fn(H) -> Result<I, E6>,
+    E1: Into<E>,
+    E2: Into<E>,
+    E3: Into<E>,
+    E4: Into<E>,
+    E5: Into<E>,
+    E6: Into<E>,
+{
+    move |a: A| {
+        let b = f(a).map_err(Into::into)?;
+        let c = g(b).map_err(Into::into)?;
+        let d = h(c).map_err(Into::into)?;
+        let e = i(d).map_err(Into::into)?;
+        let x = j(e).map_err(Into::into)?;
+        k(x).map_err(Into::into)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -74,7 +279,7 @@ mod tests {
     #[test]
     fn test_chain_vec_empty() {
         let f = chain_vec(|_: i32| Vec::<i32>::new(), |x| vec![x * 2]);
-        assert_eq!(f(3), vec![]);
+        assert_eq!(f(3), Vec::<i32>::new());
     }
 
     #[test]
@@ -100,4 +305,60 @@ mod tests {
         let f = chain!(str_to_int, double, to_string);
         assert_eq!(f("oops"), None);
     }
+
+    #[test]
+    fn test_chain3_success() {
+        let f = chain3(str_to_int, double, to_string);
+        assert_eq!(f("4"), Some("Number: 8".to_string()));
+    }
+
+    #[test]
+    fn test_chain6_short_circuits() {
+        let halve = |n: i32| if n % 2 == 0 { Some(n / 2) } else { None };
+        let f = chain6(str_to_int, double, double, halve, halve, halve);
+        assert_eq!(f("3"), None); // 3 -> 6 -> 12 -> 6 -> 3 -> None
+        assert_eq!(f("2"), Some(1));
+    }
+
+    #[test]
+    fn test_chain3_throwing() {
+        let f = chain3_throwing(
+            |s: &str| s.parse::<i32>().map_err(|_| "parse"),
+            |n| if n > 0 { Ok(n * 2) } else { Err("not positive") },
+            |n| Ok::<_, &str>(format!("#{}", n)),
+        );
+        assert_eq!(f("3"), Ok("#6".to_string()));
+        assert_eq!(f("-3"), Err("not positive"));
+        assert_eq!(f("x"), Err("parse"));
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum PipelineError {
+        Parse(std::num::ParseIntError),
+        Range(String),
+    }
+
+    impl From<std::num::ParseIntError> for PipelineError {
+        fn from(e: std::num::ParseIntError) -> Self {
+            PipelineError::Parse(e)
+        }
+    }
+
+    impl From<String> for PipelineError {
+        fn from(e: String) -> Self {
+            PipelineError::Range(e)
+        }
+    }
+
+    #[test]
+    fn test_chain2_throwing_converts_errors() {
+        let f = chain2_throwing(
+            |s: &str| s.parse::<u8>(),
+            |n| if n < 100 { Ok(n) } else { Err(format!("{} out of range", n)) },
+        );
+        let ok: Result<u8, PipelineError> = f("42");
+        assert_eq!(ok, Ok(42));
+        assert_eq!(f("200"), Err(PipelineError::Range("200 out of range".to_string())));
+        assert!(matches!(f("x"), Err(PipelineError::Parse(_))));
+    }
 }
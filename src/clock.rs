@@ -0,0 +1,95 @@
+use alloc::rc::Rc;
+use core::cell::Cell;
+use core::time::Duration;
+
+/// A monotonic time source, read as the time elapsed since some fixed origin.
+/// The timing combinators take one so they also run where `std::time::Instant`
+/// is unavailable (e.g. `wasm32-unknown-unknown`, where it panics). Any
+/// `Fn() -> Duration` closure is a clock, so in the browser `performance.now()`
+/// can be wrapped directly.
+pub trait Clock {
+    fn now(&self) -> Duration;
+}
+
+impl<F> Clock for F
+where
+    F: Fn() -> Duration,
+{
+    fn now(&self) -> Duration {
+        self()
+    }
+}
+
+/// The platform's monotonic clock (`std::time::Instant`); the default for
+/// `throttled`, `memoize_ttl` and `timed`. Not available on `wasm32-unknown-unknown`.
+#[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdClock;
+
+#[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+impl Clock for StdClock {
+    fn now(&self) -> Duration {
+        static ORIGIN: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+        ORIGIN.get_or_init(std::time::Instant::now).elapsed()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time, so one
+/// handle can be given to a combinator and the other kept to advance it.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    now: Rc<Cell<Duration>>,
+}
+
+impl ManualClock {
+    pub fn new(start: Duration) -> Self {
+        Self { now: Rc::new(Cell::new(start)) }
+    }
+
+    pub fn set(&self, now: Duration) {
+        self.now.set(now);
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_clones_share_time() {
+        let clock = ManualClock::default();
+        let handle = clock.clone();
+        handle.advance(Duration::from_millis(250));
+        assert_eq!(clock.now(), Duration::from_millis(250));
+        clock.set(Duration::from_secs(3));
+        assert_eq!(handle.now(), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_closures_are_clocks() {
+        let ticks = Cell::new(0u64);
+        let clock = || {
+            ticks.set(ticks.get() + 1);
+            Duration::from_millis(ticks.get() * 10)
+        };
+        assert_eq!(clock.now(), Duration::from_millis(10));
+        assert_eq!(clock.now(), Duration::from_millis(20));
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+    fn test_std_clock_is_monotonic() {
+        let earlier = StdClock.now();
+        assert!(StdClock.now() >= earlier);
+    }
+}
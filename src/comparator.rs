@@ -0,0 +1,138 @@
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+
+use crate::prelude::*;
+use crate::keypath::KeyPath;
+
+/// An ordering on `T`: a wrapped `Fn(&T, &T) -> Ordering` that can be reversed and
+/// chained, for sorting by several fields. Pass `into_fn()` to `sort_by`.
+pub struct Comparator<T: ?Sized, F = Box<dyn Fn(&T, &T) -> Ordering>> {
+    f: F,
+    _marker: PhantomData<fn(&T, &T) -> Ordering>,
+}
+
+impl<T: ?Sized, F> Comparator<T, F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    pub fn new(f: F) -> Self {
+        Self { f, _marker: PhantomData }
+    }
+
+    pub fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.f)(a, b)
+    }
+
+    pub fn reversed(self) -> Comparator<T, impl Fn(&T, &T) -> Ordering> {
+        let f = self.f;
+        Comparator::new(move |a: &T, b: &T| f(b, a))
+    }
+
+    /// Break ties with `other`.
+    pub fn then<G>(self, other: Comparator<T, G>) -> Comparator<T, impl Fn(&T, &T) -> Ordering>
+    where
+        G: Fn(&T, &T) -> Ordering,
+    {
+        let (f, g) = (self.f, other.f);
+        Comparator::new(move |a: &T, b: &T| f(a, b).then_with(|| g(a, b)))
+    }
+
+    pub fn into_fn(self) -> F {
+        self.f
+    }
+
+    pub fn boxed(self) -> Comparator<T>
+    where
+        F: 'static,
+    {
+        Comparator::new(Box::new(self.f) as Box<dyn Fn(&T, &T) -> Ordering>)
+    }
+}
+
+/// Order by a key extracted from each value.
+pub fn comparing<T: ?Sized, K, F>(key: F) -> Comparator<T, impl Fn(&T, &T) -> Ordering>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    Comparator::new(move |a: &T, b: &T| key(a).cmp(&key(b)))
+}
+
+/// Order by the value a keypath points at, without cloning it.
+pub fn comparing_by_keypath<Root, Value: Ord>(keypath: KeyPath<Root, Value>) -> Comparator<Root, impl Fn(&Root, &Root) -> Ordering> {
+    Comparator::new(move |a: &Root, b: &Root| (keypath.get)(a).cmp((keypath.get)(b)))
+}
+
+/// Tuples of keypaths on the same root, compared field by field from left to right.
+pub trait KeyPaths<Root> {
+    fn compare(&self, a: &Root, b: &Root) -> Ordering;
+}
+
+macro_rules! keypath_tuple {
+    ($($V:ident: $i:tt),+) => {
+        impl<Root, $($V: Ord),+> KeyPaths<Root> for ($(KeyPath<Root, $V>,)+) {
+            fn compare(&self, a: &Root, b: &Root) -> Ordering {
+                Ordering::Equal$(.then_with(|| (self.$i.get)(a).cmp((self.$i.get)(b))))+
+            }
+        }
+    };
+}
+
+keypath_tuple!(V1: 0);
+keypath_tuple!(V1: 0, V2: 1);
+keypath_tuple!(V1: 0, V2: 1, V3: 2);
+keypath_tuple!(V1: 0, V2: 1, V3: 2, V4: 3);
+keypath_tuple!(V1: 0, V2: 1, V3: 2, V4: 3, V5: 4);
+keypath_tuple!(V1: 0, V2: 1, V3: 2, V4: 3, V5: 4, V6: 5);
+
+/// Order by several keypaths: the first one, ties broken by the second, and so on.
+pub fn comparing_by_keys<Root, K: KeyPaths<Root>>(keys: K) -> Comparator<Root, impl Fn(&Root, &Root) -> Ordering> {
+    Comparator::new(move |a: &Root, b: &Root| keys.compare(a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Employee {
+        name: &'static str,
+        department: &'static str,
+        salary: u32,
+    }
+
+    fn employees() -> Vec<Employee> {
+        vec![
+            Employee { name: "cy", department: "ops", salary: 50 },
+            Employee { name: "al", department: "dev", salary: 70 },
+            Employee { name: "bo", department: "dev", salary: 90 },
+        ]
+    }
+
+    #[test]
+    fn test_comparing_then_reversed() {
+        let by_department = comparing(|e: &Employee| e.department);
+        let by_salary_desc = comparing(|e: &Employee| e.salary).reversed();
+        let order = by_department.then(by_salary_desc).into_fn();
+        let mut staff = employees();
+        staff.sort_by(order);
+        assert_eq!(staff.iter().map(|e| e.name).collect::<Vec<_>>(), vec!["bo", "al", "cy"]);
+    }
+
+    #[test]
+    fn test_comparing_by_keypath() {
+        let name = KeyPath::new(|e: &Employee| &e.name, |e, n| e.name = n);
+        let cmp = comparing_by_keypath(name);
+        let staff = employees();
+        assert_eq!(cmp.compare(&staff[0], &staff[1]), Ordering::Greater);
+        assert_eq!(staff.iter().min_by(|a, b| cmp.compare(a, b)).unwrap().name, "al");
+    }
+
+    #[test]
+    fn test_boxed_comparators() {
+        let orders: Vec<Comparator<str>> = vec![comparing(|s: &str| s.len()).boxed(), Comparator::new(str::cmp).boxed()];
+        assert_eq!(orders[0].compare("bb", "a"), Ordering::Greater);
+        assert_eq!(orders[1].compare("bb", "a"), Ordering::Greater);
+        assert_eq!(orders[1].compare("a", "a"), Ordering::Equal);
+    }
+}
@@ -59,32 +59,89 @@ where
     move |a: A| f(g(h(i(a))))
 }
 
+// compose5..compose10. Arguments are listed outermost first, as above;
+// `compose_call!` nests the calls.
+macro_rules! compose_call {
+    ($a:ident; $f:ident) => {
+        $f($a)
+    };
+    ($a:ident; $f:ident, $($rest:ident),+) => {
+        $f(compose_call!($a; $($rest),+))
+    };
+}
+
+macro_rules! compose_n {
+    ($name:ident, $A:ident -> $Z:ident; $($f:ident: $F:ident($In:ident -> $Out:ident)),+) => {
+        #[allow(clippy::too_many_arguments)]
+        pub fn $name<$($Out,)+ $A, $($F),+>($($f: $F),+) -> impl Fn($A) -> $Z
+        where
+            $($F: Fn($In) -> $Out,)+
+        {
+            move |a: $A| compose_call!(a; $($f),+)
+        }
+    };
+}
+
+compose_n!(compose5, A -> G; f: F1(E -> G), g: F2(D -> E), h: F3(C -> D), i: F4(B -> C), j: F5(A -> B));
+compose_n!(compose6, A -> H; f: F1(G -> H), g: F2(E -> G), h: F3(D -> E), i: F4(C -> D), j: F5(B -> C), k: F6(A -> B));
+compose_n!(compose7, A -> I; f: F1(H -> I), g: F2(G -> H), h: F3(E -> G), i: F4(D -> E), j: F5(C -> D), k: F6(B -> C), l: F7(A -> B));
+compose_n!(compose8, A -> J; f: F1(I -> J), g: F2(H -> I), h: F3(G -> H), i: F4(E -> G), j: F5(D -> E), k: F6(C -> D), l: F7(B -> C), m: F8(A -> B));
+compose_n!(compose9, A -> K; f: F1(J -> K), g: F2(I -> J), h: F3(H -> I), i: F4(G -> H), j: F5(E -> G), k: F6(D -> E), l: F7(C -> D), m: F8(B -> C), n: F9(A -> B));
+compose_n!(compose10, A -> L; f: F1(K -> L), g: F2(J -> K), h: F3(I -> J), i: F4(H -> I), j: F5(G -> H), k: F6(E -> G), l: F7(D -> E), m: F8(C -> D), n: F9(B -> C), o: F10(A -> B));
+
 // ---------------------------------------------------
 // Throwing versions (Swift `throws` → Rust `Result`)
+// As with `chainN_throwing`, each stage may have its own error type as long as it
+// converts `Into` the error type of the whole composition. The stages are moved into
+// the returned closure and called by reference: no `Rc`, no clones, no allocation.
 // ---------------------------------------------------
 
-pub fn compose2_res<A, B, C, E, F, G>(f: F, g: G) -> impl Fn(A) -> Result<C, E>
+pub fn compose2_res<A, B, C, E, E1, E2, F, G>(f: F, g: G) -> impl Fn(A) -> Result<C, E>
 where
-    F: Fn(B) -> Result<C, E>,
-    G: Fn(A) -> Result<B, E>,
+    F: Fn(B) -> Result<C, E2>,
+    G: Fn(A) -> Result<B, E1>,
+    E1: Into<E>,
+    E2: Into<E>,
 {
-    move |a: A| g(a).and_then(|b| f(b))
+    move |a: A| f(g(a).map_err(Into::into)?).map_err(Into::into)
 }
 
-pub fn compose3_res<A, B, C, D, E, F1, F2, F3>(
+pub fn compose3_res<A, B, C, D, E, E1, E2, E3, F1, F2, F3>(
     f: F1,
     g: F2,
     h: F3,
 ) -> impl Fn(A) -> Result<D, E>
 where
-    F1: Fn(C) -> Result<D, E>,
-    F2: Fn(B) -> Result<C, E>,
-    F3: Fn(A) -> Result<B, E>,
+    F1: Fn(C) -> Result<D, E3>,
+    F2: Fn(B) -> Result<C, E2>,
+    F3: Fn(A) -> Result<B, E1>,
+    E1: Into<E>,
+    E2: Into<E>,
+    E3: Into<E>,
 {
-    move |a: A| h(a).and_then(|b| g(b)).and_then(|c| f(c))
+    move |a: A| {
+        let b = h(a).map_err(Into::into)?;
+        let c = g(b).map_err(Into::into)?;
+        f(c).map_err(Into::into)
+    }
 }
 
 
+// ---------------------------------------------------
+// Stateful stages. `compose2` needs `Fn`, so a stage that mutates captured state
+// (a counter, an accumulator) has to be composed with `compose_mut` instead.
+// ---------------------------------------------------
+
+/// `f(g(x))` for `FnMut` stages. The result is only `FnMut`: it has to be bound
+/// `mut` to be called, and cannot be shared or passed where `Fn` is required.
+pub fn compose_mut<A, B, C, F, G>(mut f: F, mut g: G) -> impl FnMut(A) -> C
+where
+    F: FnMut(B) -> C,
+    G: FnMut(A) -> B,
+{
+    move |a: A| f(g(a))
+}
+
 // ---------------------------------------------------
 // Tests
 // ---------------------------------------------------
@@ -129,7 +186,6 @@ mod tests {
         assert_eq!(comp(5), 5); // h(5)=2, g(2)=4, f(4)=5
     }
 
-        #[test]
     #[test]
     fn test_forward_compose_two() {
         let f = |x: i32| x + 1;
@@ -168,5 +224,56 @@ mod tests {
         assert_eq!(comp(0), 42);
     }
 
+    #[derive(Debug, PartialEq)]
+    enum AmountError {
+        Parse(std::num::ParseFloatError),
+        Negative(f64),
+    }
+
+    impl From<std::num::ParseFloatError> for AmountError {
+        fn from(e: std::num::ParseFloatError) -> Self {
+            AmountError::Parse(e)
+        }
+    }
+
+    #[test]
+    fn test_compose3_res_converts_errors() {
+        let non_negative = |x: f64| if x >= 0.0 { Ok(x) } else { Err(AmountError::Negative(x)) };
+        let cents = |x: f64| Ok::<i64, AmountError>((x * 100.0).round() as i64);
+        let f = compose3_res(cents, non_negative, |s: &str| s.parse::<f64>());
+        let ok: Result<i64, AmountError> = f("12.5");
+        assert_eq!(ok, Ok(1250));
+        assert_eq!(f("-1"), Err(AmountError::Negative(-1.0)));
+        assert!(matches!(f("x"), Err(AmountError::Parse(_))));
+    }
 
+    #[test]
+    fn test_compose5_and_compose10() {
+        let inc = |x: i32| x + 1;
+        let double = |x: i32| x * 2;
+        let f = compose5(|x: i32| x.to_string(), inc, double, inc, |s: &str| s.len() as i32);
+        assert_eq!(f("abc"), "9"); // inc(double(inc(3))) = 9
+        let g = compose10(double, inc, inc, inc, inc, inc, inc, inc, inc, |x: i32| x - 8);
+        assert_eq!(g(1), 2);
+    }
+
+    #[test]
+    fn test_compose_mut_with_stateful_stages() {
+        let mut seen = 0;
+        let mut total = 0;
+        {
+            let count = |x: i32| {
+                seen += 1;
+                x
+            };
+            let accumulate = |x: i32| {
+                total += x;
+                total
+            };
+            let mut running = compose_mut(accumulate, count);
+            assert_eq!(running(3), 3);
+            assert_eq!(running(4), 7);
+        }
+        assert_eq!((seen, total), (2, 7));
+    }
 }
@@ -1,3 +1,5 @@
+use crate::prelude::*;
+
 /// Concatenate pure functions (A -> A).
 pub fn concat_fn<A>(
     fs: Vec<Box<dyn Fn(A) -> A>>
@@ -11,6 +13,7 @@ pub fn concat_fn<A>(
 }
 
 /// Concatenate throwing functions (A -> Result<A, E>).
+#[allow(clippy::type_complexity)]
 pub fn concat_fn_result<A, E>(
     fs: Vec<Box<dyn Fn(A) -> Result<A, E>>>
 ) -> impl Fn(A) -> Result<A, E> {
@@ -23,6 +26,7 @@ pub fn concat_fn_result<A, E>(
 }
 
 /// Concatenate mutating functions (FnMut(&mut A)).
+#[allow(clippy::type_complexity)]
 pub fn concat_mut<A>(
     mut fs: Vec<Box<dyn FnMut(&mut A)>>
 ) -> impl FnMut(&mut A) {
@@ -34,6 +38,7 @@ pub fn concat_mut<A>(
 }
 
 /// Concatenate throwing mutating functions (FnMut(&mut A) -> Result<(), E>).
+#[allow(clippy::type_complexity)]
 pub fn concat_mut_result<A, E>(
     mut fs: Vec<Box<dyn FnMut(&mut A) -> Result<(), E>>>
 ) -> impl FnMut(&mut A) -> Result<(), E> {
@@ -45,6 +50,131 @@ pub fn concat_mut_result<A, E>(
     }
 }
 
+// ---------------------------------------------------
+// Generic (unboxed) concatenation of endomorphisms `(A) -> A`
+// concat3(f, g, h)(a) == h(g(f(a)))
+// ---------------------------------------------------
+
+pub fn concat2<A, F1, F2>(f: F1, g: F2) -> impl Fn(A) -> A
+where
+    F1: Fn(A) -> A,
+    F2: Fn(A) -> A,
+{
+    move |a: A| g(f(a))
+}
+
+pub fn concat3<A, F1, F2, F3>(f: F1, g: F2, h: F3) -> impl Fn(A) -> A
+where
+    F1: Fn(A) -> A,
+    F2: Fn(A) -> A,
+    F3: Fn(A) -> A,
+{
+    move |a: A| h(g(f(a)))
+}
+
+pub fn concat4<A, F1, F2, F3, F4>(f: F1, g: F2, h: F3, i: F4) -> impl Fn(A) -> A
+where
+    F1: Fn(A) -> A,
+    F2: Fn(A) -> A,
+    F3: Fn(A) -> A,
+    F4: Fn(A) -> A,
+{
+    move |a: A| i(h(g(f(a))))
+}
+
+pub fn concat5<A, F1, F2, F3, F4, F5>(f: F1, g: F2, h: F3, i: F4, j: F5) -> impl Fn(A) -> A
+where
+    F1: Fn(A) -> A,
+    F2: Fn(A) -> A,
+    F3: Fn(A) -> A,
+    F4: Fn(A) -> A,
+    F5: Fn(A) -> A,
+{
+    move |a: A| j(i(h(g(f(a)))))
+}
+
+pub fn concat6<A, F1, F2, F3, F4, F5, F6>(f: F1, g: F2, h: F3, i: F4, j: F5, k: F6) -> impl Fn(A) -> A
+where
+    F1: Fn(A) -> A,
+    F2: Fn(A) -> A,
+    F3: Fn(A) -> A,
+    F4: Fn(A) -> A,
+    F5: Fn(A) -> A,
+    F6: Fn(A) -> A,
+{
+    move |a: A| k(j(i(h(g(f(a))))))
+}
+
+/// Concatenate any number of functions `(A) -> A` of the same type, e.g. a `Vec` of
+/// keypath setters or of `Box<dyn Fn(A) -> A>`. An empty list yields the identity.
+pub fn concat_all<A, F>(fs: impl IntoIterator<Item = F>) -> impl Fn(A) -> A
+where
+    F: Fn(A) -> A,
+{
+    let fs: Vec<F> = fs.into_iter().collect();
+    move |a: A| fs.iter().fold(a, |acc, f| f(acc))
+}
+
+// Throwing versions short-circuit on the first `Err`.
+
+pub fn concat2_throwing<A, E, F1, F2>(f: F1, g: F2) -> impl Fn(A) -> Result<A, E>
+where
+    F1: Fn(A) -> Result<A, E>,
+    F2: Fn(A) -> Result<A, E>,
+{
+    move |a: A| f(a).and_then(&g)
+}
+
+pub fn concat3_throwing<A, E, F1, F2, F3>(f: F1, g: F2, h: F3) -> impl Fn(A) -> Result<A, E>
+where
+    F1: Fn(A) -> Result<A, E>,
+    F2: Fn(A) -> Result<A, E>,
+    F3: Fn(A) -> Result<A, E>,
+{
+    move |a: A| f(a).and_then(&g).and_then(&h)
+}
+
+pub fn concat4_throwing<A, E, F1, F2, F3, F4>(f: F1, g: F2, h: F3, i: F4) -> impl Fn(A) -> Result<A, E>
+where
+    F1: Fn(A) -> Result<A, E>,
+    F2: Fn(A) -> Result<A, E>,
+    F3: Fn(A) -> Result<A, E>,
+    F4: Fn(A) -> Result<A, E>,
+{
+    move |a: A| f(a).and_then(&g).and_then(&h).and_then(&i)
+}
+
+pub fn concat5_throwing<A, E, F1, F2, F3, F4, F5>(f: F1, g: F2, h: F3, i: F4, j: F5) -> impl Fn(A) -> Result<A, E>
+where
+    F1: Fn(A) -> Result<A, E>,
+    F2: Fn(A) -> Result<A, E>,
+    F3: Fn(A) -> Result<A, E>,
+    F4: Fn(A) -> Result<A, E>,
+    F5: Fn(A) -> Result<A, E>,
+{
+    move |a: A| f(a).and_then(&g).and_then(&h).and_then(&i).and_then(&j)
+}
+
+pub fn concat6_throwing<A, E, F1, F2, F3, F4, F5, F6>(f: F1, g: F2, h: F3, i: F4, j: F5, k: F6) -> impl Fn(A) -> Result<A, E>
+where
+    F1: Fn(A) -> Result<A, E>,
+    F2: Fn(A) -> Result<A, E>,
+    F3: Fn(A) -> Result<A, E>,
+    F4: Fn(A) -> Result<A, E>,
+    F5: Fn(A) -> Result<A, E>,
+    F6: Fn(A) -> Result<A, E>,
+{
+    move |a: A| f(a).and_then(&g).and_then(&h).and_then(&i).and_then(&j).and_then(&k)
+}
+
+pub fn concat_all_throwing<A, E, F>(fs: impl IntoIterator<Item = F>) -> impl Fn(A) -> Result<A, E>
+where
+    F: Fn(A) -> Result<A, E>,
+{
+    let fs: Vec<F> = fs.into_iter().collect();
+    move |a: A| fs.iter().try_fold(a, |acc, f| f(acc))
+}
+
 // ---- Separate macros ----
 
 #[macro_export]
@@ -120,4 +250,71 @@ mod tests {
         let mut neg = -1;
         assert_eq!(f(&mut neg), Err("bad"));
     }
+
+    #[test]
+    fn test_concat3() {
+        let f = concat3(|x: i32| x + 1, |x| x * 2, |x| x - 3);
+        assert_eq!(f(2), 3); // ((2+1) * 2) - 3 = 3
+    }
+
+    #[test]
+    fn test_concat6_keypath_setters() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Config {
+            retries: u32,
+            verbose: bool,
+        }
+        let retries = crate::keypath::Lens::new(|c: &Config| &c.retries, |c: &mut Config, v| c.retries = v);
+        let verbose = crate::keypath::Lens::new(|c: &Config| &c.verbose, |c: &mut Config, v| c.verbose = v);
+        let configure = concat6(
+            retries.set_value(1),
+            retries.over(|r| r * 10),
+            verbose.set_value(true),
+            retries.over(|r| r + 1),
+            verbose.over(|v| v),
+            retries.over(|r| r + 2),
+        );
+        let config = configure(Config { retries: 0, verbose: false });
+        assert_eq!(config, Config { retries: 13, verbose: true });
+    }
+
+    #[test]
+    fn test_concat_all() {
+        let steps: Vec<Box<dyn Fn(String) -> String>> = vec![
+            Box::new(|s: String| s.trim().to_string()),
+            Box::new(|s: String| s.to_uppercase()),
+            Box::new(|s: String| format!("<{}>", s)),
+        ];
+        let normalize = concat_all(steps);
+        assert_eq!(normalize("  usd ".to_string()), "<USD>");
+    }
+
+    #[test]
+    fn test_concat_all_empty_is_identity() {
+        let f = concat_all(Vec::<fn(i32) -> i32>::new());
+        assert_eq!(f(7), 7);
+    }
+
+    #[test]
+    fn test_concat2_throwing() {
+        let f = concat2_throwing(
+            |x: i32| if x > 0 { Ok(x + 1) } else { Err("neg") },
+            |x| Ok(x * 2),
+        );
+        assert_eq!(f(1), Ok(4));
+        assert_eq!(f(-1), Err("neg"));
+    }
+
+    #[test]
+    fn test_concat_all_throwing_short_circuits() {
+        use std::cell::Cell;
+        let calls = Cell::new(0);
+        let step = |x: i32| {
+            calls.set(calls.get() + 1);
+            if x < 3 { Ok(x + 1) } else { Err(x) }
+        };
+        let f = concat_all_throwing(vec![&step, &step, &step, &step, &step]);
+        assert_eq!(f(0), Err(3));
+        assert_eq!(calls.get(), 4);
+    }
 }
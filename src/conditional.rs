@@ -0,0 +1,116 @@
+// Branching inside pipelines, without breaking out into `if`/`match`.
+
+use crate::prelude::*;
+
+/// Apply `f` only when `predicate` holds; otherwise pass the value through unchanged.
+pub fn when<A, P, F>(predicate: P, f: F) -> impl Fn(A) -> A
+where
+    P: Fn(&A) -> bool,
+    F: Fn(A) -> A,
+{
+    move |a: A| if predicate(&a) { f(a) } else { a }
+}
+
+/// Apply `f` only when `predicate` does not hold; otherwise pass the value through unchanged.
+pub fn unless<A, P, F>(predicate: P, f: F) -> impl Fn(A) -> A
+where
+    P: Fn(&A) -> bool,
+    F: Fn(A) -> A,
+{
+    move |a: A| if predicate(&a) { a } else { f(a) }
+}
+
+/// `f` when `predicate` holds, `g` otherwise.
+pub fn if_else<A, B, P, F, G>(predicate: P, f: F, g: G) -> impl Fn(A) -> B
+where
+    P: Fn(&A) -> bool,
+    F: Fn(A) -> B,
+    G: Fn(A) -> B,
+{
+    move |a: A| if predicate(&a) { f(a) } else { g(a) }
+}
+
+/// One arm of [`cond`]: a condition and the function to run when it is the first to hold.
+pub type Branch<'a, A, B> = (Box<dyn Fn(&A) -> bool + 'a>, Box<dyn Fn(A) -> B + 'a>);
+
+/// Build a [`Branch`] from plain closures.
+pub fn branch<'a, A, B, P, F>(predicate: P, f: F) -> Branch<'a, A, B>
+where
+    P: Fn(&A) -> bool + 'a,
+    F: Fn(A) -> B + 'a,
+{
+    (Box::new(predicate), Box::new(f))
+}
+
+/// Dispatch to the first branch whose condition holds, or to `default` if none does.
+/// Branches are tried in order, so list them by priority.
+pub fn cond<'a, A: 'a, B: 'a, D>(branches: Vec<Branch<'a, A, B>>, default: D) -> impl Fn(A) -> B + 'a
+where
+    D: Fn(A) -> B + 'a,
+{
+    move |a: A| match branches.iter().find(|(predicate, _)| predicate(&a)) {
+        Some((_, f)) => f(a),
+        None => default(a),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compose::compose3;
+
+    #[test]
+    fn test_when_and_unless() {
+        let cap = when(|n: &u32| *n > 100, |_| 100);
+        assert_eq!(cap(250), 100);
+        assert_eq!(cap(7), 7);
+        let default_name = unless(|s: &String| !s.is_empty(), |_| "anonymous".to_string());
+        assert_eq!(default_name(String::new()), "anonymous");
+        assert_eq!(default_name("ann".to_string()), "ann");
+    }
+
+    #[test]
+    fn test_if_else_changes_type() {
+        let describe = if_else(|n: &i32| *n < 0, |n| format!("debit {}", -n), |n| format!("credit {n}"));
+        assert_eq!(describe(-5), "debit 5");
+        assert_eq!(describe(3), "credit 3");
+    }
+
+    #[test]
+    fn test_in_pipeline() {
+        let normalize = compose3(
+            when(|s: &String| s.len() > 5, |s: String| s[..5].to_string()),
+            |s: String| s.trim().to_string(),
+            unless(|s: &String| s.is_empty(), |s: String| s.to_lowercase()),
+        );
+        assert_eq!(normalize("  HELLO WORLD ".to_string()), "hello");
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Action {
+        Alarm,
+        Heat,
+        Nothing,
+    }
+
+    #[test]
+    fn test_cond_first_matching_branch_wins() {
+        let rule = cond(
+            vec![
+                branch(|t: &f64| *t > 60.0, |_| Action::Alarm),
+                branch(|t: &f64| *t > 50.0, |_| Action::Nothing),
+                branch(|t: &f64| *t < 18.0, |_| Action::Heat),
+            ],
+            |_| Action::Nothing,
+        );
+        assert_eq!(rule(70.0), Action::Alarm);
+        assert_eq!(rule(10.0), Action::Heat);
+        assert_eq!(rule(20.0), Action::Nothing);
+    }
+
+    #[test]
+    fn test_cond_with_no_branches_uses_default() {
+        let f = cond(Vec::new(), |n: i32| n * 2);
+        assert_eq!(f(21), 42);
+    }
+}
@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use core::cell::RefCell;
 
 // Curry functions for Rust
 pub fn curry2<A1, A2, R, F>(function: F) -> impl Fn(A1) -> Arc<dyn Fn(A2) -> R + Send + Sync>
@@ -28,6 +31,7 @@ where
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn curry3<A1, A2, A3, R, F>(function: F) -> impl Fn(A1) -> Arc<dyn Fn(A2) -> Arc<dyn Fn(A3) -> R + Send + Sync> + Send + Sync>
 where
     F: Fn(A1, A2, A3) -> R + Send + Sync + Copy + 'static,
@@ -46,9 +50,239 @@ where
     }
 }
 
+// ---------------------------------------------------
+// Currying `FnMut` closures, e.g. ones drawing from an RNG or filling a cache.
+// Every partial application shares the one function through an `Rc<RefCell<_>>`,
+// so the results are `FnMut` but neither `Send` nor re-entrant: a curried
+// function must not call itself while it is running.
+// ---------------------------------------------------
+
+/// A partially applied `FnMut`, as returned by `curry_mut` and `curry3_mut`.
+pub type CurriedMut<'a, A, R> = Box<dyn FnMut(A) -> R + 'a>;
+
+pub fn curry_mut<'a, A1, A2, R, F>(function: F) -> impl Fn(A1) -> CurriedMut<'a, A2, R>
+where
+    F: FnMut(A1, A2) -> R + 'a,
+    A1: Clone + 'a,
+{
+    let function = Rc::new(RefCell::new(function));
+    move |a1: A1| {
+        let function = Rc::clone(&function);
+        Box::new(move |a2: A2| (function.borrow_mut())(a1.clone(), a2))
+    }
+}
+
+pub fn curry3_mut<'a, A1, A2, A3, R, F>(function: F) -> impl Fn(A1) -> CurriedMut<'a, A2, CurriedMut<'a, A3, R>>
+where
+    F: FnMut(A1, A2, A3) -> R + 'a,
+    A1: Clone + 'a,
+    A2: Clone + 'a,
+{
+    let function = Rc::new(RefCell::new(function));
+    move |a1: A1| {
+        let function = Rc::clone(&function);
+        Box::new(move |a2: A2| {
+            let function = Rc::clone(&function);
+            let a1 = a1.clone();
+            Box::new(move |a3: A3| (function.borrow_mut())(a1.clone(), a2.clone(), a3)) as CurriedMut<'a, A3, R>
+        })
+    }
+}
+
+// ---------------------------------------------------
+// Allocation-free currying
+// `curry2_unboxed(f)(a1).call(a2)`. Call syntax, `curry2_unboxed(f)(a1)(a2)`, needs a
+// nightly compiler: building with `RUSTFLAGS="--cfg overture_nightly"` implements `Fn`
+// for these structs. It is a cfg rather than a feature so that `--all-features` still
+// builds on stable.
+// ---------------------------------------------------
+
+/// A two-argument function with its leading arguments applied, stored inline.
+///
+/// On stable Rust the remaining argument is applied with `.call(a2)`: `Curried2` is
+/// not a closure and cannot be called as `curried(a2)` or passed where an `Fn` is
+/// expected. `--cfg overture_nightly` implements the `Fn` traits for it on nightly.
+#[derive(Clone, Copy)]
+pub struct Curried2<F, Args> {
+    function: F,
+    args: Args,
+}
+
+impl<F, A1> Curried2<F, (A1,)> {
+    pub fn call<A2, R>(&self, a2: A2) -> R
+    where
+        F: Fn(A1, A2) -> R,
+        A1: Clone,
+    {
+        (self.function)(self.args.0.clone(), a2)
+    }
+}
+
+/// A three-argument function with its leading arguments applied, stored inline.
+/// As with `Curried2`, arguments are applied with `.call(..)` on stable Rust and
+/// with call syntax only under `--cfg overture_nightly`.
+#[derive(Clone, Copy)]
+pub struct Curried3<F, Args> {
+    function: F,
+    args: Args,
+}
+
+impl<F, A1> Curried3<F, (A1,)> {
+    pub fn call<A2>(&self, a2: A2) -> Curried3<F, (A1, A2)>
+    where
+        F: Clone,
+        A1: Clone,
+    {
+        Curried3 {
+            function: self.function.clone(),
+            args: (self.args.0.clone(), a2),
+        }
+    }
+}
+
+impl<F, A1, A2> Curried3<F, (A1, A2)> {
+    pub fn call<A3, R>(&self, a3: A3) -> R
+    where
+        F: Fn(A1, A2, A3) -> R,
+        A1: Clone,
+        A2: Clone,
+    {
+        (self.function)(self.args.0.clone(), self.args.1.clone(), a3)
+    }
+}
+
+/// Curry without allocating: `curry2_unboxed(f)(a1).call(a2)`, or `(a1)(a2)` with
+/// `--cfg overture_nightly`.
+pub fn curry2_unboxed<A1, A2, R, F>(function: F) -> impl Fn(A1) -> Curried2<F, (A1,)>
+where
+    F: Fn(A1, A2) -> R + Clone,
+{
+    move |a1: A1| Curried2 {
+        function: function.clone(),
+        args: (a1,),
+    }
+}
+
+/// Curry without allocating: `curry3_unboxed(f)(a1).call(a2).call(a3)`, or
+/// `(a1)(a2)(a3)` with `--cfg overture_nightly`.
+pub fn curry3_unboxed<A1, A2, A3, R, F>(function: F) -> impl Fn(A1) -> Curried3<F, (A1,)>
+where
+    F: Fn(A1, A2, A3) -> R + Clone,
+{
+    move |a1: A1| Curried3 {
+        function: function.clone(),
+        args: (a1,),
+    }
+}
+
+#[cfg(overture_nightly)]
+mod nightly {
+    use super::{Curried2, Curried3};
+
+    impl<F, A1, A2, R> FnOnce<(A2,)> for Curried2<F, (A1,)>
+    where
+        F: Fn(A1, A2) -> R,
+        A1: Clone,
+    {
+        type Output = R;
+        extern "rust-call" fn call_once(self, (a2,): (A2,)) -> R {
+            (self.function)(self.args.0, a2)
+        }
+    }
+
+    impl<F, A1, A2, R> FnMut<(A2,)> for Curried2<F, (A1,)>
+    where
+        F: Fn(A1, A2) -> R,
+        A1: Clone,
+    {
+        extern "rust-call" fn call_mut(&mut self, (a2,): (A2,)) -> R {
+            Curried2::call(self, a2)
+        }
+    }
+
+    impl<F, A1, A2, R> Fn<(A2,)> for Curried2<F, (A1,)>
+    where
+        F: Fn(A1, A2) -> R,
+        A1: Clone,
+    {
+        extern "rust-call" fn call(&self, (a2,): (A2,)) -> R {
+            Curried2::call(self, a2)
+        }
+    }
+
+    impl<F, A1, A2> FnOnce<(A2,)> for Curried3<F, (A1,)>
+    where
+        F: Clone,
+        A1: Clone,
+    {
+        type Output = Curried3<F, (A1, A2)>;
+        extern "rust-call" fn call_once(self, (a2,): (A2,)) -> Self::Output {
+            Curried3 {
+                function: self.function,
+                args: (self.args.0, a2),
+            }
+        }
+    }
+
+    impl<F, A1, A2> FnMut<(A2,)> for Curried3<F, (A1,)>
+    where
+        F: Clone,
+        A1: Clone,
+    {
+        extern "rust-call" fn call_mut(&mut self, (a2,): (A2,)) -> Self::Output {
+            Curried3::<F, (A1,)>::call(self, a2)
+        }
+    }
+
+    impl<F, A1, A2> Fn<(A2,)> for Curried3<F, (A1,)>
+    where
+        F: Clone,
+        A1: Clone,
+    {
+        extern "rust-call" fn call(&self, (a2,): (A2,)) -> Self::Output {
+            Curried3::<F, (A1,)>::call(self, a2)
+        }
+    }
+
+    impl<F, A1, A2, A3, R> FnOnce<(A3,)> for Curried3<F, (A1, A2)>
+    where
+        F: Fn(A1, A2, A3) -> R,
+        A1: Clone,
+        A2: Clone,
+    {
+        type Output = R;
+        extern "rust-call" fn call_once(self, (a3,): (A3,)) -> R {
+            (self.function)(self.args.0, self.args.1, a3)
+        }
+    }
+
+    impl<F, A1, A2, A3, R> FnMut<(A3,)> for Curried3<F, (A1, A2)>
+    where
+        F: Fn(A1, A2, A3) -> R,
+        A1: Clone,
+        A2: Clone,
+    {
+        extern "rust-call" fn call_mut(&mut self, (a3,): (A3,)) -> R {
+            Curried3::<F, (A1, A2)>::call(self, a3)
+        }
+    }
+
+    impl<F, A1, A2, A3, R> Fn<(A3,)> for Curried3<F, (A1, A2)>
+    where
+        F: Fn(A1, A2, A3) -> R,
+        A1: Clone,
+        A2: Clone,
+    {
+        extern "rust-call" fn call(&self, (a3,): (A3,)) -> R {
+            Curried3::<F, (A1, A2)>::call(self, a3)
+        }
+    }
+}
+
 // Macro for higher arity functions - using Arc pattern
 macro_rules! curry {
     ($name:ident, $($arg:ident),+) => {
+        #[allow(non_snake_case)]
         pub fn $name<F, R, $($arg),+>(function: F) -> impl Fn($($arg),+) -> R
         where
             F: Fn($($arg),+) -> R + Copy + 'static,
@@ -180,4 +414,75 @@ mod tests {
         
         assert_eq!(handle.join().unwrap(), 8);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_curry_mut_shares_state_between_applications() {
+        let mut seed = 7u32;
+        let roll = |sides: u32, bonus: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            seed % sides + 1 + bonus
+        };
+        let dice = curry_mut(roll);
+        let mut d6 = dice(6);
+        let mut d20 = dice(20);
+        let rolls = [d6(0), d20(5), d6(0)];
+        assert!((1..=6).contains(&rolls[0]) && (1..=6).contains(&rolls[2]));
+        assert!((6..=25).contains(&rolls[1]));
+    }
+
+    #[test]
+    fn test_curry3_mut_with_cache() {
+        let mut cache = std::collections::HashMap::new();
+        let mut misses = 0;
+        let lookup = |table: &str, column: &str, id: u32| {
+            cache
+                .entry((table.to_string(), column.to_string(), id))
+                .or_insert_with(|| {
+                    misses += 1;
+                    format!("{}.{}#{}", table, column, id)
+                })
+                .clone()
+        };
+        let by_table = curry3_mut(lookup);
+        let mut user_names = by_table("users")("name");
+        assert_eq!(user_names(1), "users.name#1");
+        assert_eq!(user_names(1), "users.name#1");
+        assert_eq!(by_table("orders")("total")(1), "orders.total#1");
+        drop(user_names);
+        drop(by_table);
+        assert_eq!(misses, 2);
+    }
+
+    #[test]
+    fn test_curry2_unboxed() {
+        let add = |a: i32, b: i32| a + b;
+        let add2 = curry2_unboxed(add)(2);
+        assert_eq!(add2.call(3), 5);
+        assert_eq!(add2.call(7), 9);
+    }
+
+    #[test]
+    fn test_curry3_unboxed() {
+        let multiply_add = |a: i32, b: i32, c: i32| a * b + c;
+        let times_2_plus = curry3_unboxed(multiply_add)(2).call(3);
+        assert_eq!(times_2_plus.call(4), 10);
+        assert_eq!(times_2_plus.call(0), 6);
+    }
+
+    #[test]
+    fn test_curry_unboxed_clones_bound_args() {
+        let greet = |greeting: String, name: &str| format!("{}, {}!", greeting, name);
+        let hello = curry2_unboxed(greet)("Hello".to_string());
+        assert_eq!(hello.call("Ann"), "Hello, Ann!");
+        assert_eq!(hello.call("Bob"), "Hello, Bob!");
+    }
+
+    #[cfg(overture_nightly)]
+    #[test]
+    fn test_curried_call_syntax() {
+        let multiply_add = |a: i32, b: i32, c: i32| a * b + c;
+        assert_eq!(curry3_unboxed(multiply_add)(2)(3)(4), 10);
+        let squares: Vec<i32> = (1..4).map(curry2_unboxed(|a: i32, b: i32| a * b)(2)).collect();
+        assert_eq!(squares, vec![2, 4, 6]);
+    }
+}
@@ -0,0 +1,194 @@
+// Decision tables: rows of conditions (one per input column) and an outcome, compiled
+// into a single total function. Rows are tried in order, the first match wins, and an
+// explicit default covers every input no row matches.
+
+use core::fmt;
+
+use crate::prelude::*;
+
+/// One cell of a row: matches any value of its column, or exactly one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Match<T> {
+    Any,
+    Is(T),
+}
+
+impl<T: PartialEq> Match<T> {
+    pub fn matches(&self, value: &T) -> bool {
+        match self {
+            Match::Any => true,
+            Match::Is(expected) => expected == value,
+        }
+    }
+
+    /// Some value matches both cells.
+    fn overlaps(&self, other: &Match<T>) -> bool {
+        match (self, other) {
+            (Match::Is(a), Match::Is(b)) => a == b,
+            _ => true,
+        }
+    }
+
+    /// Every value matching `other` also matches `self`.
+    fn subsumes(&self, other: &Match<T>) -> bool {
+        match (self, other) {
+            (Match::Any, _) => true,
+            (Match::Is(a), Match::Is(b)) => a == b,
+            (Match::Is(_), Match::Any) => false,
+        }
+    }
+}
+
+/// A tuple of `Match` cells, one per input column.
+pub trait Conditions {
+    type Input;
+
+    fn matches(&self, input: &Self::Input) -> bool;
+    fn overlaps(&self, other: &Self) -> bool;
+    fn subsumes(&self, other: &Self) -> bool;
+}
+
+macro_rules! conditions_tuple {
+    ($($T:ident: $i:tt),+) => {
+        impl<$($T: PartialEq),+> Conditions for ($(Match<$T>,)+) {
+            type Input = ($($T,)+);
+
+            fn matches(&self, input: &Self::Input) -> bool {
+                $(self.$i.matches(&input.$i))&&+
+            }
+
+            fn overlaps(&self, other: &Self) -> bool {
+                $(self.$i.overlaps(&other.$i))&&+
+            }
+
+            fn subsumes(&self, other: &Self) -> bool {
+                $(self.$i.subsumes(&other.$i))&&+
+            }
+        }
+    };
+}
+
+conditions_tuple!(T1: 0);
+conditions_tuple!(T1: 0, T2: 1);
+conditions_tuple!(T1: 0, T2: 1, T3: 2);
+conditions_tuple!(T1: 0, T2: 1, T3: 2, T4: 3);
+conditions_tuple!(T1: 0, T2: 1, T3: 2, T4: 3, T5: 4);
+conditions_tuple!(T1: 0, T2: 1, T3: 2, T4: 3, T5: 4, T6: 5);
+
+/// A problem found in a table. Rows are numbered from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowIssue {
+    /// Some inputs match both rows; the earlier one wins for them.
+    Overlap { row: usize, earlier: usize },
+    /// Every input matching `row` already matches `shadowed_by`, so `row` never fires.
+    Unreachable { row: usize, shadowed_by: usize },
+}
+
+impl fmt::Display for RowIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RowIssue::Overlap { row, earlier } => write!(f, "row {row} overlaps row {earlier}"),
+            RowIssue::Unreachable { row, shadowed_by } => write!(f, "row {row} is unreachable (shadowed by row {shadowed_by})"),
+        }
+    }
+}
+
+/// Rows of conditions and outcomes plus the default outcome.
+pub struct DecisionTable<C, O> {
+    rows: Vec<(C, O)>,
+    default: O,
+}
+
+impl<C: Conditions, O: Clone> DecisionTable<C, O> {
+    pub fn new(rows: Vec<(C, O)>, default: O) -> Self {
+        Self { rows, default }
+    }
+
+    /// Every overlapping and unreachable row. A row is reported unreachable when a
+    /// single earlier row covers it; only an overlap is reported otherwise.
+    pub fn issues(&self) -> Vec<RowIssue> {
+        let mut issues = Vec::new();
+        for (row, (conditions, _)) in self.rows.iter().enumerate() {
+            let earlier_rows = self.rows[..row].iter().enumerate();
+            if let Some((earlier, _)) = earlier_rows.clone().find(|(_, (c, _))| c.subsumes(conditions)) {
+                issues.push(RowIssue::Unreachable { row, shadowed_by: earlier });
+            } else {
+                issues.extend(
+                    earlier_rows
+                        .filter(|(_, (c, _))| c.overlaps(conditions))
+                        .map(|(earlier, _)| RowIssue::Overlap { row, earlier }),
+                );
+            }
+        }
+        issues
+    }
+
+    /// The table as a function, or the unreachable rows if there are any.
+    /// Overlaps are allowed: they are how priorities are expressed.
+    pub fn compile(self) -> Result<impl Fn(C::Input) -> O, Vec<RowIssue>> {
+        let unreachable: Vec<RowIssue> =
+            self.issues().into_iter().filter(|issue| matches!(issue, RowIssue::Unreachable { .. })).collect();
+        if !unreachable.is_empty() {
+            return Err(unreachable);
+        }
+        Ok(move |input: C::Input| {
+            self.rows
+                .iter()
+                .find(|(conditions, _)| conditions.matches(&input))
+                .map_or_else(|| self.default.clone(), |(_, outcome)| outcome.clone())
+        })
+    }
+}
+
+/// Compile rows and a default into a function; see [`DecisionTable::compile`].
+pub fn decision_table<C: Conditions, O: Clone>(rows: Vec<(C, O)>, default: O) -> Result<impl Fn(C::Input) -> O, Vec<RowIssue>> {
+    DecisionTable::new(rows, default).compile()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Match::{Any, Is};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Tier {
+        Gold,
+        Basic,
+    }
+
+    #[test]
+    fn test_first_matching_row_wins() {
+        let discount = decision_table(
+            vec![
+                ((Is(Tier::Gold), Is("NL")), 20),
+                ((Is(Tier::Gold), Any), 10),
+                ((Any, Is("NL")), 5),
+            ],
+            0,
+        )
+        .unwrap();
+        assert_eq!(discount((Tier::Gold, "NL")), 20);
+        assert_eq!(discount((Tier::Gold, "DE")), 10);
+        assert_eq!(discount((Tier::Basic, "NL")), 5);
+        assert_eq!(discount((Tier::Basic, "DE")), 0);
+    }
+
+    #[test]
+    fn test_unreachable_rows_are_rejected() {
+        let rows = vec![((Any, Is(true)), "a"), ((Is(1), Is(true)), "b"), ((Is(1), Any), "c")];
+        let table = DecisionTable::new(rows.clone(), "default");
+        assert_eq!(
+            table.issues(),
+            vec![RowIssue::Unreachable { row: 1, shadowed_by: 0 }, RowIssue::Overlap { row: 2, earlier: 0 }, RowIssue::Overlap { row: 2, earlier: 1 }]
+        );
+        let err = decision_table(rows, "default").err().unwrap();
+        assert_eq!(err, vec![RowIssue::Unreachable { row: 1, shadowed_by: 0 }]);
+        assert_eq!(err[0].to_string(), "row 1 is unreachable (shadowed by row 0)");
+    }
+
+    #[test]
+    fn test_empty_table_is_the_default() {
+        let f = decision_table(Vec::<((Match<u8>,), &str)>::new(), "none").unwrap();
+        assert_eq!(f((3,)), "none");
+    }
+}
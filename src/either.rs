@@ -0,0 +1,87 @@
+/// One of two values, neither of which is an error: like `Result` without the bias.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> Either<L, R> {
+    pub fn is_left(&self) -> bool {
+        matches!(self, Either::Left(_))
+    }
+
+    pub fn is_right(&self) -> bool {
+        matches!(self, Either::Right(_))
+    }
+
+    pub fn left(self) -> Option<L> {
+        match self {
+            Either::Left(l) => Some(l),
+            Either::Right(_) => None,
+        }
+    }
+
+    pub fn right(self) -> Option<R> {
+        match self {
+            Either::Left(_) => None,
+            Either::Right(r) => Some(r),
+        }
+    }
+
+    pub fn map_left<L2>(self, f: impl FnOnce(L) -> L2) -> Either<L2, R> {
+        match self {
+            Either::Left(l) => Either::Left(f(l)),
+            Either::Right(r) => Either::Right(r),
+        }
+    }
+
+    pub fn map_right<R2>(self, f: impl FnOnce(R) -> R2) -> Either<L, R2> {
+        match self {
+            Either::Left(l) => Either::Left(l),
+            Either::Right(r) => Either::Right(f(r)),
+        }
+    }
+
+    /// Collapse both sides into one value.
+    pub fn either<B>(self, f: impl FnOnce(L) -> B, g: impl FnOnce(R) -> B) -> B {
+        match self {
+            Either::Left(l) => f(l),
+            Either::Right(r) => g(r),
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Either<E, T> {
+    fn from(r: Result<T, E>) -> Self {
+        match r {
+            Ok(t) => Either::Right(t),
+            Err(e) => Either::Left(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accessors() {
+        let l: Either<i32, &str> = Either::Left(1);
+        assert!(l.is_left());
+        assert_eq!(l.left(), Some(1));
+        assert_eq!(l.right(), None);
+    }
+
+    #[test]
+    fn test_map_and_either() {
+        let r: Either<i32, &str> = Either::Right("abc");
+        assert_eq!(r.map_right(str::len), Either::Right(3));
+        assert_eq!(r.map_left(|n| n * 2).either(|n| n.to_string(), str::to_uppercase), "ABC");
+    }
+
+    #[test]
+    fn test_from_result() {
+        assert_eq!(Either::from(Ok::<_, &str>(1)), Either::Right(1));
+        assert_eq!(Either::from(Err::<i32, _>("e")), Either::Left("e"));
+    }
+}
@@ -0,0 +1,122 @@
+// Swift `flip`: move the first ("self") argument of a function to the end,
+// turning method-style `(self, args...)` functions into data-last form.
+// flip(f)(b, a) == f(a, b)
+// flip3(f)(b, c, a) == f(a, b, c)
+
+pub fn flip<A1, A2, R, F>(function: F) -> impl Fn(A2, A1) -> R
+where
+    F: Fn(A1, A2) -> R,
+{
+    move |a2, a1| function(a1, a2)
+}
+
+pub fn flip3<A1, A2, A3, R, F>(function: F) -> impl Fn(A2, A3, A1) -> R
+where
+    F: Fn(A1, A2, A3) -> R,
+{
+    move |a2, a3, a1| function(a1, a2, a3)
+}
+
+pub fn flip4<A1, A2, A3, A4, R, F>(function: F) -> impl Fn(A2, A3, A4, A1) -> R
+where
+    F: Fn(A1, A2, A3, A4) -> R,
+{
+    move |a2, a3, a4, a1| function(a1, a2, a3, a4)
+}
+
+pub fn flip5<A1, A2, A3, A4, A5, R, F>(function: F) -> impl Fn(A2, A3, A4, A5, A1) -> R
+where
+    F: Fn(A1, A2, A3, A4, A5) -> R,
+{
+    move |a2, a3, a4, a5, a1| function(a1, a2, a3, a4, a5)
+}
+
+pub fn flip6<A1, A2, A3, A4, A5, A6, R, F>(function: F) -> impl Fn(A2, A3, A4, A5, A6, A1) -> R
+where
+    F: Fn(A1, A2, A3, A4, A5, A6) -> R,
+{
+    move |a2, a3, a4, a5, a6, a1| function(a1, a2, a3, a4, a5, a6)
+}
+
+// ---------------------------------------------------
+// Throwing versions (Swift `throws` → Rust `Result`)
+// ---------------------------------------------------
+
+pub fn flip_throwing<A1, A2, R, E, F>(function: F) -> impl Fn(A2, A1) -> Result<R, E>
+where
+    F: Fn(A1, A2) -> Result<R, E>,
+{
+    move |a2, a1| function(a1, a2)
+}
+
+pub fn flip3_throwing<A1, A2, A3, R, E, F>(function: F) -> impl Fn(A2, A3, A1) -> Result<R, E>
+where
+    F: Fn(A1, A2, A3) -> Result<R, E>,
+{
+    move |a2, a3, a1| function(a1, a2, a3)
+}
+
+pub fn flip4_throwing<A1, A2, A3, A4, R, E, F>(function: F) -> impl Fn(A2, A3, A4, A1) -> Result<R, E>
+where
+    F: Fn(A1, A2, A3, A4) -> Result<R, E>,
+{
+    move |a2, a3, a4, a1| function(a1, a2, a3, a4)
+}
+
+pub fn flip5_throwing<A1, A2, A3, A4, A5, R, E, F>(function: F) -> impl Fn(A2, A3, A4, A5, A1) -> Result<R, E>
+where
+    F: Fn(A1, A2, A3, A4, A5) -> Result<R, E>,
+{
+    move |a2, a3, a4, a5, a1| function(a1, a2, a3, a4, a5)
+}
+
+pub fn flip6_throwing<A1, A2, A3, A4, A5, A6, R, E, F>(function: F) -> impl Fn(A2, A3, A4, A5, A6, A1) -> Result<R, E>
+where
+    F: Fn(A1, A2, A3, A4, A5, A6) -> Result<R, E>,
+{
+    move |a2, a3, a4, a5, a6, a1| function(a1, a2, a3, a4, a5, a6)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flip() {
+        let sub = |a: i32, b: i32| a - b;
+        let flipped = flip(sub);
+        assert_eq!(flipped(1, 10), 9);
+    }
+
+    #[test]
+    fn test_flip3_data_last() {
+        let repeat = |s: &str, times: usize, sep: &str| vec![s; times].join(sep);
+        let repeat_data_last = flip3(repeat);
+        assert_eq!(repeat_data_last(3, "-", "ab"), "ab-ab-ab");
+    }
+
+    #[test]
+    fn test_flip6_rotates_first_to_end() {
+        let f = |a: u8, b: u8, c: u8, d: u8, e: u8, f: u8| [a, b, c, d, e, f];
+        let flipped = flip6(f);
+        assert_eq!(flipped(2, 3, 4, 5, 6, 1), [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_flip_throwing() {
+        let checked_div = |a: i32, b: i32| a.checked_div(b).ok_or("division by zero");
+        let divide_by = flip_throwing(checked_div);
+        assert_eq!(divide_by(2, 10), Ok(5));
+        assert_eq!(divide_by(0, 10), Err("division by zero"));
+    }
+
+    #[test]
+    fn test_flip4_throwing() {
+        let clamp = |x: i32, lo: i32, hi: i32, name: &str| {
+            if lo > hi { Err(format!("{}: empty range", name)) } else { Ok(x.clamp(lo, hi)) }
+        };
+        let clamp_last = flip4_throwing(clamp);
+        assert_eq!(clamp_last(0, 10, "x", 42), Ok(10));
+        assert_eq!(clamp_last(10, 0, "x", 42), Err("x: empty range".to_string()));
+    }
+}
@@ -0,0 +1,267 @@
+use core::marker::PhantomData;
+use core::ops::{Shl, Shr};
+
+use crate::prelude::*;
+
+/// A function `(A) -> B` wrapped in a type with combinator methods.
+/// `Func<A, B>` (the default `F`) is boxed and can be stored in struct fields;
+/// `Func<A, B, F>` keeps the concrete closure type and stays allocation-free.
+pub struct Func<A, B, F = Box<dyn Fn(A) -> B>> {
+    f: F,
+    _marker: PhantomData<fn(A) -> B>,
+}
+
+/// The boxed `Func`, named for storing pipelines in `Vec`s, maps and struct fields.
+/// Build one with `.boxed()`; `then`/`and_then` keep working on it.
+pub type BoxFn<A, B> = Func<A, B>;
+
+/// What a `Func` wraps: any `Fn(A) -> B`, or a [`Then`] built by `>>`/`<<`.
+pub trait Stage<A> {
+    type Output;
+    fn run(&self, a: A) -> Self::Output;
+}
+
+impl<A, B, F> Stage<A> for F
+where
+    F: Fn(A) -> B,
+{
+    type Output = B;
+    fn run(&self, a: A) -> B {
+        self(a)
+    }
+}
+
+/// Two stages run one after the other, `second(first(a))`. The output of `f >> g`;
+/// unlike a closure it can be named, so the operators don't have to box.
+pub struct Then<F, G> {
+    first: F,
+    second: G,
+}
+
+impl<A, F, G> Stage<A> for Then<F, G>
+where
+    F: Stage<A>,
+    G: Stage<F::Output>,
+{
+    type Output = G::Output;
+    fn run(&self, a: A) -> G::Output {
+        self.second.run(self.first.run(a))
+    }
+}
+
+impl<F: Clone, G: Clone> Clone for Then<F, G> {
+    fn clone(&self) -> Self {
+        Self { first: self.first.clone(), second: self.second.clone() }
+    }
+}
+
+impl<A, B, F> Func<A, B, F>
+where
+    F: Fn(A) -> B,
+{
+    pub fn new(f: F) -> Self {
+        Self { f, _marker: PhantomData }
+    }
+}
+
+impl<A, B, F> Func<A, B, F>
+where
+    F: Stage<A, Output = B>,
+{
+    pub fn call(&self, a: A) -> B {
+        self.f.run(a)
+    }
+
+    /// Borrow as a plain closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(A) -> B + '_ {
+        move |a| self.f.run(a)
+    }
+
+    pub fn into_inner(self) -> F {
+        self.f
+    }
+
+    /// Forward composition: `f.then(g)` == `g(f(a))`
+    pub fn then<C, G>(self, g: G) -> Func<A, C, impl Fn(A) -> C>
+    where
+        G: Fn(B) -> C,
+    {
+        let f = self.f;
+        Func::new(move |a: A| g(f.run(a)))
+    }
+
+    /// Backward composition: `f.compose(g)` == `f(g(z))`
+    pub fn compose<Z, G>(self, g: G) -> Func<Z, B, impl Fn(Z) -> B>
+    where
+        G: Fn(Z) -> A,
+    {
+        let f = self.f;
+        Func::new(move |z: Z| f.run(g(z)))
+    }
+
+    /// Erase the closure type so differently-built pipelines share one type.
+    pub fn boxed(self) -> Func<A, B>
+    where
+        F: 'static,
+    {
+        let f = self.f;
+        Func::new(Box::new(move |a: A| f.run(a)) as Box<dyn Fn(A) -> B>)
+    }
+}
+
+// Throwing functions (Swift `throws` → Rust `Result`)
+impl<A, T, E, F> Func<A, Result<T, E>, F>
+where
+    F: Stage<A, Output = Result<T, E>>,
+{
+    /// Chain another fallible step, short-circuiting on `Err`.
+    pub fn and_then<C, G>(self, g: G) -> Func<A, Result<C, E>, impl Fn(A) -> Result<C, E>>
+    where
+        G: Fn(T) -> Result<C, E>,
+    {
+        let f = self.f;
+        Func::new(move |a: A| f.run(a).and_then(&g))
+    }
+
+    /// Transform the success value, leaving errors untouched.
+    pub fn map_output<C, G>(self, g: G) -> Func<A, Result<C, E>, impl Fn(A) -> Result<C, E>>
+    where
+        G: Fn(T) -> C,
+    {
+        let f = self.f;
+        Func::new(move |a: A| f.run(a).map(&g))
+    }
+}
+
+// ---------------------------------------------------
+// Operators: `f >> g` (Swift `>>>`) and `f << g` (Swift `<<<`)
+// The result is `Func<A, C, Then<F, G>>`: no allocation, and the stages are called
+// statically, so `a >> b >> c` compiles like `c(b(a(x)))` and needs no `'static`.
+// ---------------------------------------------------
+
+impl<A, B, C, F, G> Shr<Func<B, C, G>> for Func<A, B, F>
+where
+    F: Stage<A, Output = B>,
+    G: Stage<B, Output = C>,
+{
+    type Output = Func<A, C, Then<F, G>>;
+
+    fn shr(self, rhs: Func<B, C, G>) -> Self::Output {
+        Func { f: Then { first: self.f, second: rhs.f }, _marker: PhantomData }
+    }
+}
+
+impl<Z, A, B, F, G> Shl<Func<Z, A, G>> for Func<A, B, F>
+where
+    F: Stage<A, Output = B>,
+    G: Stage<Z, Output = A>,
+{
+    type Output = Func<Z, B, Then<G, F>>;
+
+    fn shl(self, rhs: Func<Z, A, G>) -> Self::Output {
+        Func { f: Then { first: rhs.f, second: self.f }, _marker: PhantomData }
+    }
+}
+
+impl<A, B, F: Clone> Clone for Func<A, B, F> {
+    fn clone(&self) -> Self {
+        Self { f: self.f.clone(), _marker: PhantomData }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_and_then() {
+        let f = Func::new(|x: i32| x + 1).then(|x| x * 2).then(|x| x.to_string());
+        assert_eq!(f.call(3), "8");
+    }
+
+    #[test]
+    fn test_compose() {
+        let f = Func::new(|x: i32| x + 1).compose(|s: &str| s.len() as i32);
+        assert_eq!(f.call("abc"), 4);
+    }
+
+    #[test]
+    fn test_as_fn_in_map() {
+        let square = Func::new(|x: i32| x * x);
+        let squares: Vec<i32> = (1..=3).map(square.as_fn()).collect();
+        assert_eq!(squares, vec![1, 4, 9]);
+    }
+
+    #[test]
+    fn test_boxed_in_struct_field() {
+        struct Stage {
+            name: &'static str,
+            run: Func<i32, i32>,
+        }
+        let stages = [
+            Stage { name: "inc", run: Func::new(|x: i32| x + 1).boxed() },
+            Stage { name: "double", run: Func::new(|x: i32| x * 2).then(|x| x - 1).boxed() },
+        ];
+        let result = stages.iter().fold(5, |acc, stage| stage.run.call(acc));
+        assert_eq!(result, 11);
+        assert_eq!(stages[1].name, "double");
+    }
+
+    #[test]
+    fn test_and_then_and_map_output() {
+        let parse = Func::new(|s: &str| s.parse::<i32>().map_err(|e| e.to_string()));
+        let pipeline = parse
+            .and_then(|n| if n >= 0 { Ok(n) } else { Err("negative".to_string()) })
+            .map_output(|n| n * 10);
+        assert_eq!(pipeline.call("4"), Ok(40));
+        assert_eq!(pipeline.call("-4"), Err("negative".to_string()));
+        assert!(pipeline.call("x").is_err());
+    }
+
+    #[test]
+    fn test_shr_forward_composition() {
+        let f = Func::new(|x: i32| x + 1) >> Func::new(|x: i32| x * 2) >> Func::new(|x: i32| x - 3);
+        assert_eq!(f.call(5), 9); // ((5+1)*2)-3
+    }
+
+    #[test]
+    fn test_shl_backward_composition() {
+        let f = Func::new(|x: i32| x + 1) << Func::new(|x: i32| x * 2);
+        assert_eq!(f.call(3), 7); // (3*2)+1
+    }
+
+    #[test]
+    fn test_operators_match_hand_written() {
+        let pipeline = Func::new(|x: u64| x.wrapping_mul(31))
+            >> Func::new(|x: u64| x ^ 0xAB)
+            >> Func::new(|x: u64| x.rotate_left(7));
+        let by_hand = |x: u64| (x.wrapping_mul(31) ^ 0xAB).rotate_left(7);
+        for x in 0..1_000 {
+            assert_eq!(pipeline.call(x), by_hand(x));
+        }
+    }
+
+    #[test]
+    fn test_box_fn_registry() {
+        use std::collections::HashMap;
+        let mut fees: HashMap<&str, BoxFn<u64, u64>> = HashMap::new();
+        fees.insert("card", Func::new(|cents: u64| cents * 3 / 100).then(|fee| fee.max(30)).boxed());
+        fees.insert("transfer", Func::new(|_: u64| 0).boxed());
+        assert_eq!(fees["card"].call(10_000), 300);
+        assert_eq!(fees["card"].call(100), 30);
+        assert_eq!(fees["transfer"].call(10_000), 0);
+    }
+
+    #[test]
+    fn test_operators_stay_unboxed() {
+        fn inc(x: i32) -> i32 {
+            x + 1
+        }
+        let offset = 10;
+        let pipeline = Func::new(inc) >> Func::new(|x: i32| x * 2) >> Func::new(move |x: i32| x - offset);
+        // Fn items and non-capturing closures are zero-sized, so only the captured `offset` remains.
+        assert_eq!(core::mem::size_of_val(&pipeline), core::mem::size_of::<i32>());
+        let stored: BoxFn<i32, i32> = (Func::new(inc) << pipeline).boxed();
+        assert_eq!(stored.call(5), 3); // ((5+1)*2)-10+1
+    }
+}
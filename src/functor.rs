@@ -0,0 +1,225 @@
+// Lightweight Functor / Applicative / Monad emulation. Rust has no higher-kinded types,
+// so each trait names "the same container holding a `B`" through the `Wrapped<B>`
+// generic associated type. This lets combinators such as `map2`, `lift2` and `traverse`
+// be written once for `Option`, `Result` and `Validated`. `Vec` is only `Mappable`: its
+// lawful applicative is the cartesian product, which would need `Clone` items.
+
+use crate::prelude::*;
+use crate::validated::Validated;
+
+/// Containers whose contents can be transformed: `Option::map`, `Result::map`, ...
+pub trait Mappable {
+    type Item;
+    type Wrapped<B>;
+
+    fn fmap<B>(self, f: impl FnMut(Self::Item) -> B) -> Self::Wrapped<B>;
+}
+
+/// Containers that can wrap a plain value and combine two wrapped values.
+pub trait Applicative: Mappable {
+    fn pure(a: Self::Item) -> Self;
+
+    fn zip_with<B, C>(self, other: Self::Wrapped<B>, f: impl FnMut(Self::Item, B) -> C) -> Self::Wrapped<C>;
+}
+
+/// Containers whose contents can feed a step producing another container: `and_then`.
+pub trait Chainable: Applicative {
+    fn chain<B>(self, f: impl FnMut(Self::Item) -> Self::Wrapped<B>) -> Self::Wrapped<B>;
+}
+
+/// Apply a two-argument function inside any applicative.
+pub fn map2<FA, B, C>(a: FA, b: FA::Wrapped<B>, f: impl FnMut(FA::Item, B) -> C) -> FA::Wrapped<C>
+where
+    FA: Applicative,
+{
+    a.zip_with(b, f)
+}
+
+/// Lift a two-argument function to work on any applicative.
+pub fn lift2<FA, B, C, F>(f: F) -> impl Fn(FA, FA::Wrapped<B>) -> FA::Wrapped<C>
+where
+    FA: Applicative,
+    F: Fn(FA::Item, B) -> C,
+{
+    move |a: FA, b: FA::Wrapped<B>| map2(a, b, &f)
+}
+
+/// Run `f` over every item and gather the results inside the applicative: the first
+/// `None`/`Err` for `Option`/`Result`, every error for `Validated`. Unlike
+/// `options::traverse`, `f` is called for every item.
+pub fn traverse<I, FB, F>(items: I, f: F) -> FB::Wrapped<Vec<FB::Item>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> FB,
+    FB: Applicative,
+    FB::Wrapped<Vec<FB::Item>>: Applicative<
+            Item = Vec<FB::Item>,
+            Wrapped<FB::Item> = FB,
+            Wrapped<Vec<FB::Item>> = FB::Wrapped<Vec<FB::Item>>,
+        >,
+{
+    items.into_iter().map(f).fold(Applicative::pure(Vec::new()), |acc: FB::Wrapped<Vec<FB::Item>>, fb| {
+        acc.zip_with(fb, |mut items, item| {
+            items.push(item);
+            items
+        })
+    })
+}
+
+/// `traverse` with the identity: turn a collection of wrapped values inside out.
+pub fn sequence<I, FB>(items: I) -> FB::Wrapped<Vec<FB::Item>>
+where
+    I: IntoIterator<Item = FB>,
+    FB: Applicative,
+    FB::Wrapped<Vec<FB::Item>>: Applicative<
+            Item = Vec<FB::Item>,
+            Wrapped<FB::Item> = FB,
+            Wrapped<Vec<FB::Item>> = FB::Wrapped<Vec<FB::Item>>,
+        >,
+{
+    traverse(items, |fb| fb)
+}
+
+// --- Option
+
+impl<T> Mappable for Option<T> {
+    type Item = T;
+    type Wrapped<B> = Option<B>;
+
+    fn fmap<B>(self, f: impl FnMut(T) -> B) -> Option<B> {
+        self.map(f)
+    }
+}
+
+impl<T> Applicative for Option<T> {
+    fn pure(a: T) -> Self {
+        Some(a)
+    }
+
+    fn zip_with<B, C>(self, other: Option<B>, mut f: impl FnMut(T, B) -> C) -> Option<C> {
+        Some(f(self?, other?))
+    }
+}
+
+impl<T> Chainable for Option<T> {
+    fn chain<B>(self, f: impl FnMut(T) -> Option<B>) -> Option<B> {
+        self.and_then(f)
+    }
+}
+
+// --- Result (short-circuits on the first error)
+
+impl<T, E> Mappable for Result<T, E> {
+    type Item = T;
+    type Wrapped<B> = Result<B, E>;
+
+    fn fmap<B>(self, f: impl FnMut(T) -> B) -> Result<B, E> {
+        self.map(f)
+    }
+}
+
+impl<T, E> Applicative for Result<T, E> {
+    fn pure(a: T) -> Self {
+        Ok(a)
+    }
+
+    fn zip_with<B, C>(self, other: Result<B, E>, mut f: impl FnMut(T, B) -> C) -> Result<C, E> {
+        Ok(f(self?, other?))
+    }
+}
+
+impl<T, E> Chainable for Result<T, E> {
+    fn chain<B>(self, f: impl FnMut(T) -> Result<B, E>) -> Result<B, E> {
+        self.and_then(f)
+    }
+}
+
+// --- Vec
+
+impl<T> Mappable for Vec<T> {
+    type Item = T;
+    type Wrapped<B> = Vec<B>;
+
+    fn fmap<B>(self, f: impl FnMut(T) -> B) -> Vec<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+// --- Validated (accumulates errors, so it is deliberately not `Chainable`)
+
+impl<T, E> Mappable for Validated<T, E> {
+    type Item = T;
+    type Wrapped<B> = Validated<B, E>;
+
+    fn fmap<B>(self, f: impl FnMut(T) -> B) -> Validated<B, E> {
+        self.map(f)
+    }
+}
+
+impl<T, E> Applicative for Validated<T, E> {
+    fn pure(a: T) -> Self {
+        Validated::Valid(a)
+    }
+
+    fn zip_with<B, C>(self, other: Validated<B, E>, mut f: impl FnMut(T, B) -> C) -> Validated<C, E> {
+        self.zip(other).map(|(a, b)| f(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_generic<FA: Applicative<Item = i32>>(a: FA, b: FA::Wrapped<i32>) -> FA::Wrapped<i32> {
+        map2(a, b, |x, y| x + y)
+    }
+
+    #[test]
+    fn test_map2_written_once() {
+        assert_eq!(add_generic(Some(1), Some(2)), Some(3));
+        assert_eq!(add_generic(Some(1), None), None);
+        assert_eq!(add_generic(Ok::<_, &str>(1), Err("bad")), Err("bad"));
+    }
+
+    #[test]
+    fn test_validated_accumulates() {
+        let v = map2(Validated::<i32, &str>::invalid("a"), Validated::invalid("b"), |x: i32, y: i32| x + y);
+        assert_eq!(v, Validated::Invalid(vec!["a", "b"]));
+    }
+
+    #[test]
+    fn test_fmap_and_pure() {
+        assert_eq!(Option::pure(2).fmap(|x| x * 3), Some(6));
+        assert_eq!(<Result<_, ()>>::pure("x").fmap(str::len), Ok(1));
+    }
+
+    #[test]
+    fn test_chain() {
+        assert_eq!(Some(4).chain(|x| if x > 3 { Some(x) } else { None }), Some(4));
+        assert_eq!(Ok::<_, &str>(1).chain(|_| Err::<i32, _>("stop")), Err("stop"));
+    }
+
+    #[test]
+    fn test_lift2_generic() {
+        let add = |x: i32, y: i32| x + y;
+        assert_eq!(lift2(add)(Some(1), Some(2)), Some(3));
+        assert_eq!(lift2(add)(Ok::<_, &str>(1), Err("bad")), Err("bad"));
+        assert_eq!(lift2(add)(Validated::<_, &str>::invalid("a"), Validated::invalid("b")), Validated::Invalid(vec!["a", "b"]));
+    }
+
+    #[test]
+    fn test_traverse_and_sequence_written_once() {
+        let parse = |s: &str| s.parse::<i32>().ok();
+        assert_eq!(traverse(["1", "2"], parse), Some(vec![1, 2]));
+        assert_eq!(traverse(["1", "x"], parse), None);
+        let check = |n: i32| if n > 0 { Validated::Valid(n) } else { Validated::invalid(n) };
+        assert_eq!(traverse([1, -2, 3, -4], check), Validated::Invalid(vec![-2, -4]));
+        assert_eq!(sequence(vec![Ok::<_, &str>(1), Ok(2)]), Ok(vec![1, 2]));
+        assert_eq!(sequence(Vec::<Option<i32>>::new()), Some(vec![]));
+    }
+
+    #[test]
+    fn test_vec_fmap() {
+        assert_eq!(vec![1, 2].fmap(|x| x * 10), vec![10, 20]);
+    }
+}
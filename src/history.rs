@@ -0,0 +1,135 @@
+use alloc::collections::VecDeque;
+
+use crate::prelude::*;
+use crate::patch::Patch;
+
+/// A value with undo/redo: every change goes through a `Patch`, whose inverse is
+/// what `undo` applies. Only the last `max_depth` changes can be undone.
+pub struct History<Root: 'static> {
+    state: Root,
+    undo_stack: VecDeque<Patch<Root>>,
+    redo_stack: Vec<Patch<Root>>,
+    max_depth: usize,
+}
+
+impl<Root: 'static> History<Root> {
+    pub fn new(state: Root, max_depth: usize) -> Self {
+        Self { state, undo_stack: VecDeque::new(), redo_stack: Vec::new(), max_depth }
+    }
+
+    pub fn state(&self) -> &Root {
+        &self.state
+    }
+
+    pub fn into_state(self) -> Root {
+        self.state
+    }
+
+    /// Apply a change. Anything that could be redone is discarded, and the oldest
+    /// change is forgotten once more than `max_depth` are held.
+    pub fn apply(&mut self, patch: Patch<Root>) {
+        patch.apply(&mut self.state);
+        self.redo_stack.clear();
+        self.undo_stack.push_back(patch);
+        if self.undo_stack.len() > self.max_depth {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    /// Revert the latest change; `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.undo_stack.pop_back() {
+            Some(patch) => {
+                patch.invert().apply(&mut self.state);
+                self.redo_stack.push(patch);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Re-apply the latest undone change; `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(patch) => {
+                patch.apply(&mut self.state);
+                self.undo_stack.push_back(patch);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keypath::KeyPath;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Doc {
+        title: String,
+        size: u32,
+    }
+
+    fn size() -> KeyPath<Doc, u32> {
+        KeyPath::new(|d: &Doc| &d.size, |d, v| d.size = v)
+    }
+
+    fn resize(history: &History<Doc>, to: u32) -> Patch<Doc> {
+        let mut doc = history.state().clone();
+        let mut patch = Patch::new();
+        patch.set(&mut doc, "size", size(), to);
+        patch
+    }
+
+    fn doc() -> Doc {
+        Doc { title: "notes".into(), size: 12 }
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = History::new(doc(), 10);
+        history.apply(resize(&history, 14));
+        history.apply(resize(&history, 16));
+        assert!(history.undo());
+        assert_eq!(history.state().size, 14);
+        assert!(history.undo());
+        assert_eq!(history.state(), &doc());
+        assert!(!history.undo());
+        assert!(history.redo());
+        assert_eq!(history.state().size, 14);
+    }
+
+    #[test]
+    fn test_apply_clears_redo() {
+        let mut history = History::new(doc(), 10);
+        history.apply(resize(&history, 14));
+        history.undo();
+        assert!(history.can_redo());
+        history.apply(resize(&history, 20));
+        assert!(!history.can_redo());
+        assert!(!history.redo());
+        assert_eq!(history.into_state().size, 20);
+    }
+
+    #[test]
+    fn test_bounded_depth() {
+        let mut history = History::new(doc(), 2);
+        for size in [13, 14, 15] {
+            history.apply(resize(&history, size));
+        }
+        assert!(history.undo());
+        assert!(history.undo());
+        assert!(!history.can_undo(), "only two changes are kept");
+        assert_eq!(history.state().size, 13);
+    }
+}
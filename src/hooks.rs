@@ -0,0 +1,128 @@
+// Observers for throwing functions: peek at `Ok`/`Err` values flowing through a
+// composed pipeline without changing them and without `map_err` at every seam.
+
+use crate::prelude::*;
+
+/// Call `log` with every error produced by `f`; the result is passed through unchanged.
+pub fn log_err<A, B, E, F, L>(f: F, log: L) -> impl Fn(A) -> Result<B, E>
+where
+    F: Fn(A) -> Result<B, E>,
+    L: Fn(&E),
+{
+    move |a: A| f(a).inspect_err(&log)
+}
+
+/// Call `log` with every success value produced by `f`; the result is passed through unchanged.
+pub fn log_ok<A, B, E, F, L>(f: F, log: L) -> impl Fn(A) -> Result<B, E>
+where
+    F: Fn(A) -> Result<B, E>,
+    L: Fn(&B),
+{
+    move |a: A| f(a).inspect(&log)
+}
+
+type OkHook<'a> = Box<dyn Fn(usize) + 'a>;
+type ErrHook<'a, E> = Box<dyn Fn(usize, &E) + 'a>;
+
+/// Pipeline-wide observers, attached to each stage with `stage(index, f)`.
+/// Error hooks receive the index of the stage that failed.
+pub struct Hooks<'a, E> {
+    on_ok: Option<OkHook<'a>>,
+    on_err: Option<ErrHook<'a, E>>,
+}
+
+impl<'a, E> Hooks<'a, E> {
+    pub fn new() -> Self {
+        Self { on_ok: None, on_err: None }
+    }
+
+    /// Called with the stage index whenever a stage succeeds.
+    pub fn on_ok(self, f: impl Fn(usize) + 'a) -> Self {
+        Self { on_ok: Some(Box::new(f)), ..self }
+    }
+
+    /// Called with the stage index and the error whenever a stage fails.
+    pub fn on_err(self, f: impl Fn(usize, &E) + 'a) -> Self {
+        Self { on_err: Some(Box::new(f)), ..self }
+    }
+
+    /// Wrap stage number `index` of a throwing pipeline so it reports to these hooks.
+    pub fn stage<A, B, F>(&self, index: usize, f: F) -> impl Fn(A) -> Result<B, E> + '_
+    where
+        F: Fn(A) -> Result<B, E> + 'a,
+    {
+        move |a: A| {
+            let result = f(a);
+            match &result {
+                Ok(_) => {
+                    if let Some(on_ok) = &self.on_ok {
+                        on_ok(index);
+                    }
+                }
+                Err(e) => {
+                    if let Some(on_err) = &self.on_err {
+                        on_err(index, e);
+                    }
+                }
+            }
+            result
+        }
+    }
+}
+
+impl<E> Default for Hooks<'_, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::chain3_throwing;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_log_err() {
+        let logged = RefCell::new(Vec::new());
+        let parse = log_err(|s: &str| s.parse::<i32>().map_err(|e| e.to_string()), |e: &String| {
+            logged.borrow_mut().push(e.clone())
+        });
+        assert_eq!(parse("1"), Ok(1));
+        assert!(parse("x").is_err());
+        assert_eq!(logged.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_log_ok() {
+        let logged = RefCell::new(Vec::new());
+        let halve = log_ok(
+            |x: i32| if x % 2 == 0 { Ok(x / 2) } else { Err("odd") },
+            |x: &i32| logged.borrow_mut().push(*x),
+        );
+        assert_eq!(halve(8), Ok(4));
+        assert_eq!(halve(3), Err("odd"));
+        assert_eq!(*logged.borrow(), vec![4]);
+    }
+
+    #[test]
+    fn test_hooks_report_failing_stage() {
+        let errors = RefCell::new(Vec::new());
+        let successes = RefCell::new(Vec::new());
+        let hooks = Hooks::new()
+            .on_ok(|i| successes.borrow_mut().push(i))
+            .on_err(|i, e: &String| errors.borrow_mut().push((i, e.clone())));
+
+        let pipeline = chain3_throwing(
+            hooks.stage(0, |s: &str| s.parse::<i32>().map_err(|e| e.to_string())),
+            hooks.stage(1, |n: i32| if n > 0 { Ok(n) } else { Err("not positive".to_string()) }),
+            hooks.stage(2, |n: i32| Ok(n * 2)),
+        );
+
+        assert_eq!(pipeline("3"), Ok(6));
+        assert_eq!(*successes.borrow(), vec![0, 1, 2]);
+
+        assert_eq!(pipeline("-3"), Err("not positive".to_string()));
+        assert_eq!(*errors.borrow(), vec![(1, "not positive".to_string())]);
+    }
+}
@@ -0,0 +1,43 @@
+use core::convert::Infallible;
+
+/// The function returning its argument unchanged, instead of an inline `|x| x`.
+pub fn identity<A>() -> impl Fn(A) -> A + Copy {
+    |a: A| a
+}
+
+/// A function ignoring its argument and always returning `value`.
+pub fn constant<A, V: Clone>(value: V) -> impl Fn(A) -> V {
+    move |_: A| value.clone()
+}
+
+/// Turn an impossible value into any type, e.g. to unify `Result<T, Infallible>`
+/// with other results via `map_err(absurd)`.
+pub fn absurd<T>(never: Infallible) -> T {
+    match never {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compose::compose2;
+
+    #[test]
+    fn test_identity() {
+        assert_eq!(identity()(5), 5);
+        let unchanged = compose2(identity(), |s: &str| s.len());
+        assert_eq!(unchanged("abc"), 3);
+    }
+
+    #[test]
+    fn test_constant() {
+        let zero = constant::<&str, _>(0);
+        assert_eq!(["a", "b"].map(zero), [0, 0]);
+    }
+
+    #[test]
+    fn test_absurd() {
+        let infallible: Result<i32, Infallible> = Ok(1);
+        let widened: Result<i32, String> = infallible.map_err(absurd);
+        assert_eq!(widened, Ok(1));
+    }
+}
@@ -5,6 +5,18 @@ pub struct Lens<Root, Value> {
     pub set: fn(&mut Root, Value),
 }
 
+/// Swift-style name for a `Lens`: `KeyPath<Root, Value>`.
+pub type KeyPath<Root, Value> = Lens<Root, Value>;
+
+// A lens is just two fn pointers, so it is always `Copy`, whatever `Root`/`Value` are.
+impl<Root, Value> Clone for Lens<Root, Value> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Root, Value> Copy for Lens<Root, Value> {}
+
 impl<Root, Value> Lens<Root, Value> 
 {
     pub fn new(get: fn(&Root) -> &Value, set: fn(&mut Root, Value)) -> Self {
@@ -17,10 +29,19 @@ impl<Root, Value> Lens<Root, Value>
         move |root| g(root)
     }
 
+    /// Owned getter usable anywhere a plain function is expected,
+    /// e.g. `users.iter().map(age.as_fn())`.
+    pub fn as_fn(self) -> impl Fn(&Root) -> Value + Copy
+    where
+        Value: Clone,
+    {
+        let g = self.get;
+        move |root| g(root).clone()
+    }
+
     /// Immutable setter: like Swift `prop(\.field)`
     pub fn over(&self, update: impl Fn(Value) -> Value + 'static + Clone) -> impl Fn(Root) -> Root
     where
-        Root: Clone,
         Value: Clone,
     {
         let get = self.get;
@@ -36,7 +57,6 @@ impl<Root, Value> Lens<Root, Value>
     /// Set a constant value: like Swift `set(\.field, value)`
     pub fn set_value(&self, value: Value) -> impl Fn(Root) -> Root
     where
-        Root: Clone,
         Value: Clone + 'static,
     {
         self.over(move |_| value.clone())
@@ -45,7 +65,6 @@ impl<Root, Value> Lens<Root, Value>
     /// Mutable in-place setter: like Swift `mprop`
     pub fn mver(&self, update: impl Fn(&mut Value) + 'static + Clone) -> impl Fn(&mut Root) 
     where 
-        Root: Clone,
         Value: Clone
 {
         let get = self.get;
@@ -58,8 +77,136 @@ impl<Root, Value> Lens<Root, Value>
             (self.set)(root, owned);
         }
     }
+
+    /// In-place version of `over`: updates the field through `&mut Root`, so the root
+    /// is never moved or cloned (only the field value is read out).
+    pub fn over_mut(self, update: impl Fn(Value) -> Value) -> impl Fn(&mut Root)
+    where
+        Value: Clone,
+    {
+        let get = self.get;
+        let set = self.set;
+        move |root: &mut Root| {
+            let new_value = update(get(root).clone());
+            set(root, new_value);
+        }
+    }
+
+    /// In-place version of `set_value`.
+    pub fn set_mut(self, value: Value) -> impl Fn(&mut Root)
+    where
+        Value: Clone,
+    {
+        let set = self.set;
+        move |root: &mut Root| set(root, value.clone())
+    }
+
+    /// `over` only when the current value satisfies `predicate`; otherwise the root
+    /// is returned unchanged.
+    pub fn over_if(self, predicate: impl Fn(&Value) -> bool, update: impl Fn(Value) -> Value) -> impl Fn(Root) -> Root
+    where
+        Value: Clone,
+    {
+        move |mut root: Root| {
+            let current = (self.get)(&root);
+            if predicate(current) {
+                let new_value = update(current.clone());
+                (self.set)(&mut root, new_value);
+            }
+            root
+        }
+    }
+
+    /// `set_value` only when the current value satisfies `predicate`, e.g. to fill in a
+    /// default without overwriting an explicit setting.
+    pub fn set_if(self, predicate: impl Fn(&Value) -> bool, value: Value) -> impl Fn(Root) -> Root
+    where
+        Value: Clone,
+    {
+        self.over_if(predicate, move |_| value.clone())
+    }
+
+    // Throwing versions (Swift `throws` → Rust `Result`)
+
+    /// `over` with a fallible update: the root comes back updated, or the update's error.
+    pub fn over_throwing<E>(self, update: impl Fn(Value) -> Result<Value, E>) -> impl Fn(Root) -> Result<Root, E>
+    where
+        Value: Clone,
+    {
+        move |mut root: Root| {
+            let new_value = update((self.get)(&root).clone())?;
+            (self.set)(&mut root, new_value);
+            Ok(root)
+        }
+    }
+
+    /// In-place `over_throwing`; the root is left untouched when the update fails.
+    pub fn over_mut_throwing<E>(self, update: impl Fn(Value) -> Result<Value, E>) -> impl Fn(&mut Root) -> Result<(), E>
+    where
+        Value: Clone,
+    {
+        move |root: &mut Root| {
+            let new_value = update((self.get)(root).clone())?;
+            (self.set)(root, new_value);
+            Ok(())
+        }
+    }
 }
 
+/// Free-function getter: like Swift `get(\.field)`
+pub fn get<Root, Value>(keypath: KeyPath<Root, Value>) -> impl Fn(&Root) -> Value + Copy
+where
+    Value: Clone,
+{
+    keypath.as_fn()
+}
+
+/// An update that can be applied to an owned root or in place.
+pub trait Setter<Root> {
+    fn apply_mut(&self, root: &mut Root);
+
+    fn apply(&self, mut root: Root) -> Root {
+        self.apply_mut(&mut root);
+        root
+    }
+}
+
+/// Any in-place update, e.g. from `over_mut` or `set_mut`, is a setter.
+impl<Root, F> Setter<Root> for F
+where
+    F: Fn(&mut Root),
+{
+    fn apply_mut(&self, root: &mut Root) {
+        self(root)
+    }
+}
+
+/// A field update built by [`prop`].
+pub struct Prop<Root, Value, F> {
+    keypath: KeyPath<Root, Value>,
+    update: F,
+}
+
+/// Like Swift `prop(\.field)(update)`, usable in both styles: `apply` on an owned root
+/// or `apply_mut` through `&mut Root`.
+pub fn prop<Root, Value, F>(keypath: KeyPath<Root, Value>, update: F) -> Prop<Root, Value, F>
+where
+    Value: Clone,
+    F: Fn(Value) -> Value,
+{
+    Prop { keypath, update }
+}
+
+impl<Root, Value, F> Setter<Root> for Prop<Root, Value, F>
+where
+    Value: Clone,
+    F: Fn(Value) -> Value,
+{
+    fn apply_mut(&self, root: &mut Root) {
+        let new_value = (self.update)((self.keypath.get)(root).clone());
+        (self.keypath.set)(root, new_value);
+    }
+}
 
 // fn main() {
 //     let user = User {
@@ -116,6 +263,19 @@ fn name_lens() -> Lens<User, String> {
         assert_eq!((lens.get_fn())(&user), &30);
     }
 
+    #[test]
+    fn test_as_fn_in_iterator_map() {
+        let users = [
+            User { name: "Alice".into(), age: 30 },
+            User { name: "Bob".into(), age: 40 },
+        ];
+        let ages: Vec<u32> = users.iter().map(age_lens().as_fn()).collect();
+        assert_eq!(ages, vec![30, 40]);
+
+        let names: Vec<String> = users.iter().map(get(name_lens())).collect();
+        assert_eq!(names, vec!["Alice".to_string(), "Bob".to_string()]);
+    }
+
     #[test]
     fn test_immutable_update() {
         let user = User { name: "Alice".into(), age: 30 };
@@ -164,4 +324,93 @@ fn name_lens() -> Lens<User, String> {
         let wrapped = (lens.over(|age| age.saturating_add(1)))(user.clone());
         assert_eq!(wrapped.age, u32::MAX, "should saturate at max value");
     }
-}
\ No newline at end of file
+
+    /// A root that can't be cloned: in-place updates must not need to.
+    struct Ledger {
+        entries: Vec<u64>,
+        owner: String,
+    }
+
+    fn owner_lens() -> Lens<Ledger, String> {
+        Lens::new(|l: &Ledger| &l.owner, |l: &mut Ledger, v: String| l.owner = v)
+    }
+
+    #[test]
+    fn test_over_mut_and_set_mut_without_clone() {
+        let mut ledger = Ledger { entries: vec![1, 2, 3], owner: "ann".into() };
+        (owner_lens().over_mut(|o| o.to_uppercase()))(&mut ledger);
+        assert_eq!(ledger.owner, "ANN");
+        (owner_lens().set_mut("bob".into()))(&mut ledger);
+        assert_eq!(ledger.owner, "bob");
+        assert_eq!(ledger.entries.len(), 3);
+    }
+
+    #[test]
+    fn test_prop_both_styles() {
+        let birthday = prop(age_lens(), |age| age + 1);
+        let user = User { name: "Alice".into(), age: 30 };
+        let older = birthday.apply(user);
+        assert_eq!(older.age, 31);
+
+        let mut user = older;
+        birthday.apply_mut(&mut user);
+        assert_eq!(user.age, 32);
+    }
+
+    #[test]
+    fn test_setters_in_a_list() {
+        let setters: Vec<Box<dyn Setter<User>>> = vec![
+            Box::new(prop(age_lens(), |age| age * 2)),
+            Box::new(name_lens().set_mut("Zed".into())),
+        ];
+        let user = setters.iter().fold(User { name: "A".into(), age: 2 }, |u, s| s.apply(u));
+        assert_eq!(user, User { name: "Zed".into(), age: 4 });
+    }
+
+    fn normalize_currency(code: String) -> Result<String, String> {
+        if code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()) {
+            Ok(code.to_uppercase())
+        } else {
+            Err(format!("invalid currency {code}"))
+        }
+    }
+
+    #[test]
+    fn test_over_throwing() {
+        let normalize = name_lens().over_throwing(normalize_currency);
+        let ok = normalize(User { name: "eur".into(), age: 1 });
+        assert_eq!(ok.map(|u| u.name), Ok("EUR".to_string()));
+        let err = normalize(User { name: "euro".into(), age: 1 });
+        assert_eq!(err, Err("invalid currency euro".to_string()));
+    }
+
+    #[test]
+    fn test_over_mut_throwing_leaves_root_on_error() {
+        let mut ledger = Ledger { entries: vec![], owner: "e1".into() };
+        assert!((owner_lens().over_mut_throwing(normalize_currency))(&mut ledger).is_err());
+        assert_eq!(ledger.owner, "e1");
+        ledger.owner = "usd".into();
+        assert_eq!((owner_lens().over_mut_throwing(normalize_currency))(&mut ledger), Ok(()));
+        assert_eq!(ledger.owner, "USD");
+    }
+
+    #[test]
+    fn test_set_if_is_idempotent_default() {
+        let default_name = name_lens().set_if(String::is_empty, "anonymous".into());
+        let named = default_name(User { name: "".into(), age: 1 });
+        assert_eq!(named.name, "anonymous");
+        assert_eq!(default_name(named.clone()), named);
+        assert_eq!(default_name(User { name: "Ann".into(), age: 1 }).name, "Ann");
+    }
+
+    #[test]
+    fn test_over_if_in_concat() {
+        let configure = crate::concat::concat2(
+            age_lens().over_if(|age| *age < 18, |_| 18),
+            name_lens().over_if(|n| n.ends_with(' '), |n| n.trim_end().to_string()),
+        );
+        let user = configure(User { name: "Bo ".into(), age: 12 });
+        assert_eq!(user, User { name: "Bo".into(), age: 18 });
+        assert_eq!(configure(user.clone()), user);
+    }
+}
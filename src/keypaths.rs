@@ -0,0 +1,28 @@
+// `keypaths` is the module name used by the `overture_core` flavour of this API.
+// It forwards to the canonical `keypath` module.
+
+use crate::keypath;
+
+#[deprecated(note = "use `keypath::Lens`")]
+pub type Lens<Root, Value> = keypath::Lens<Root, Value>;
+
+#[deprecated(note = "use `keypath::KeyPath`")]
+pub type KeyPath<Root, Value> = keypath::KeyPath<Root, Value>;
+
+#[deprecated(note = "use `keypath::get`")]
+pub fn get<Root, Value>(keypath: keypath::KeyPath<Root, Value>) -> impl Fn(&Root) -> Value + Copy
+where
+    Value: Clone,
+{
+    keypath::get(keypath)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[allow(deprecated)]
+    fn test_shim_delegates() {
+        let lens: super::KeyPath<(u8, u8), u8> = super::Lens::new(|p| &p.1, |p, v| p.1 = v);
+        assert_eq!(super::get(lens)(&(1, 2)), 2);
+    }
+}
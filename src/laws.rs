@@ -0,0 +1,145 @@
+// Composition laws as test helpers, for checking combinators built on top of this crate.
+// `compose` is any way of joining two functions, with `compose(f, g)` running `f` first
+// (as `chain`/`then` do); wrap a backward composition as `|f, g| compose2(g, f)`.
+//
+// The `assert_*` functions check fixed inputs and panic like `assert_eq!`; the `check_*`
+// functions run the same law over values generated by a proptest `Strategy`.
+
+use std::fmt::Debug;
+
+use proptest::strategy::Strategy;
+use proptest::test_runner::{Config, TestCaseError, TestError, TestRunner};
+
+/// A boxed `(A) -> A`, the function type the laws are stated for.
+pub type EndoFn<A> = Box<dyn Fn(A) -> A>;
+
+fn boxed<A, F: Fn(A) -> A + 'static>(f: F) -> EndoFn<A> {
+    Box::new(f)
+}
+
+// No failure persistence: a library helper shouldn't write regression files or warn
+// about a missing source directory in the caller's crate.
+fn runner() -> TestRunner {
+    TestRunner::new(Config { failure_persistence: None, ..Config::default() })
+}
+
+fn associative_sides<A, C, F, G, H>(compose: &C, f: &F, g: &G, h: &H, a: A) -> (A, A)
+where
+    A: Clone + 'static,
+    C: Fn(EndoFn<A>, EndoFn<A>) -> EndoFn<A>,
+    F: Fn(A) -> A + Clone + 'static,
+    G: Fn(A) -> A + Clone + 'static,
+    H: Fn(A) -> A + Clone + 'static,
+{
+    let left = compose(compose(boxed(f.clone()), boxed(g.clone())), boxed(h.clone()));
+    let right = compose(boxed(f.clone()), compose(boxed(g.clone()), boxed(h.clone())));
+    (left(a.clone()), right(a))
+}
+
+fn identity_sides<A, C, F>(compose: &C, f: &F, a: A) -> (A, A, A)
+where
+    A: Clone + 'static,
+    C: Fn(EndoFn<A>, EndoFn<A>) -> EndoFn<A>,
+    F: Fn(A) -> A + Clone + 'static,
+{
+    let left = compose(boxed(|a| a), boxed(f.clone()));
+    let right = compose(boxed(f.clone()), boxed(|a| a));
+    (left(a.clone()), right(a.clone()), f(a))
+}
+
+/// `compose(compose(f, g), h)` and `compose(f, compose(g, h))` agree on every input.
+pub fn assert_associative<A, C, F, G, H>(compose: C, f: F, g: G, h: H, inputs: impl IntoIterator<Item = A>)
+where
+    A: Clone + Debug + PartialEq + 'static,
+    C: Fn(EndoFn<A>, EndoFn<A>) -> EndoFn<A>,
+    F: Fn(A) -> A + Clone + 'static,
+    G: Fn(A) -> A + Clone + 'static,
+    H: Fn(A) -> A + Clone + 'static,
+{
+    for a in inputs {
+        let (left, right) = associative_sides(&compose, &f, &g, &h, a.clone());
+        assert_eq!(left, right, "composition is not associative for input {a:?}");
+    }
+}
+
+/// Composing with the identity on either side gives back `f`.
+pub fn assert_identity<A, C, F>(compose: C, f: F, inputs: impl IntoIterator<Item = A>)
+where
+    A: Clone + Debug + PartialEq + 'static,
+    C: Fn(EndoFn<A>, EndoFn<A>) -> EndoFn<A>,
+    F: Fn(A) -> A + Clone + 'static,
+{
+    for a in inputs {
+        let (left, right, plain) = identity_sides(&compose, &f, a.clone());
+        assert_eq!(left, plain, "identity is not a left unit for input {a:?}");
+        assert_eq!(right, plain, "identity is not a right unit for input {a:?}");
+    }
+}
+
+/// [`assert_associative`] over generated inputs; the error holds the shrunk failing input.
+pub fn check_associative<A, C, F, G, H, S>(compose: C, f: F, g: G, h: H, inputs: S) -> Result<(), TestError<A>>
+where
+    A: Clone + Debug + PartialEq + 'static,
+    C: Fn(EndoFn<A>, EndoFn<A>) -> EndoFn<A>,
+    F: Fn(A) -> A + Clone + 'static,
+    G: Fn(A) -> A + Clone + 'static,
+    H: Fn(A) -> A + Clone + 'static,
+    S: Strategy<Value = A>,
+{
+    runner().run(&inputs, |a| {
+        let (left, right) = associative_sides(&compose, &f, &g, &h, a);
+        if left == right { Ok(()) } else { Err(TestCaseError::fail(format!("{left:?} != {right:?}"))) }
+    })
+}
+
+/// [`assert_identity`] over generated inputs.
+pub fn check_identity<A, C, F, S>(compose: C, f: F, inputs: S) -> Result<(), TestError<A>>
+where
+    A: Clone + Debug + PartialEq + 'static,
+    C: Fn(EndoFn<A>, EndoFn<A>) -> EndoFn<A>,
+    F: Fn(A) -> A + Clone + 'static,
+    S: Strategy<Value = A>,
+{
+    runner().run(&inputs, |a| {
+        let (left, right, plain) = identity_sides(&compose, &f, a);
+        if left == plain && right == plain {
+            Ok(())
+        } else {
+            Err(TestCaseError::fail(format!("{left:?}, {right:?} != {plain:?}")))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compose::compose2;
+
+    fn then(f: EndoFn<i64>, g: EndoFn<i64>) -> EndoFn<i64> {
+        Box::new(compose2(g, f))
+    }
+
+    #[test]
+    fn test_compose2_laws() {
+        let (inc, double, square) = (|x: i64| x + 1, |x: i64| x * 2, |x: i64| x * x);
+        assert_associative(then, inc, double, square, -5..5);
+        assert_identity(then, double, [0, 1, -7]);
+        assert!(check_associative(then, inc, double, square, -1000i64..1000).is_ok());
+        assert!(check_identity(then, square, -1000i64..1000).is_ok());
+    }
+
+    #[test]
+    fn test_broken_combinator_is_caught() {
+        // Adds one after every join, so the grouping decides whether `h` sees it: the two
+        // sides are `h(g(f(x)) + 1) + 1` and `h(g(f(x))) + 2`, which differ for every input.
+        let broken = |f: EndoFn<i64>, g: EndoFn<i64>| -> EndoFn<i64> { Box::new(move |x| g(f(x)) + 1) };
+        let result = check_associative(broken, |x: i64| x + 1, |x: i64| x - 1, |x: i64| x * 3, 0i64..100);
+        assert!(matches!(result, Err(TestError::Fail(_, _))));
+    }
+
+    #[test]
+    #[should_panic(expected = "not a left unit")]
+    fn test_assert_identity_panics() {
+        assert_identity(|_: EndoFn<i64>, g: EndoFn<i64>| -> EndoFn<i64> { Box::new(move |x| g(x) + 1) }, |x: i64| x, [1]);
+    }
+}
@@ -1,6 +1,80 @@
+#![cfg_attr(overture_nightly, feature(unboxed_closures, fn_traits))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+// The `alloc` items that the std prelude would provide, for modules that also build
+// without std.
+mod prelude {
+    pub use alloc::boxed::Box;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+    pub use alloc::{format, vec};
+}
+
+#[macro_use]
+mod arity;
+
 pub mod keypath;
-pub mod combinig;
+pub mod combining;
 pub mod chain;
 pub mod compose;
 pub mod concat;
 pub mod curry;
+pub mod uncurry;
+pub mod partial;
+pub mod spread;
+pub mod flip;
+pub mod zurry;
+pub mod with;
+pub mod pipe;
+pub mod tap;
+pub mod func;
+pub mod try_pipeline;
+#[cfg(feature = "std")]
+pub mod registry;
+pub mod named;
+pub mod traced;
+pub mod clock;
+#[cfg(feature = "testing")]
+pub mod laws;
+#[cfg(feature = "std")]
+pub mod memoize;
+#[cfg(feature = "std")]
+pub mod retry;
+#[cfg(feature = "std")]
+pub mod throttle;
+#[cfg(feature = "std")]
+pub mod once;
+#[cfg(feature = "std")]
+pub mod timed;
+pub mod hooks;
+pub mod result;
+pub mod options;
+pub mod bracket;
+pub mod reader;
+pub mod writer;
+pub mod non_empty;
+pub mod monoid;
+pub mod validated;
+pub mod validate;
+pub mod either;
+pub mod functor;
+pub mod arrays;
+pub mod suites;
+pub mod transduce;
+pub mod predicate;
+pub mod conditional;
+pub mod decision_table;
+pub mod arrow;
+pub mod identity;
+pub mod comparator;
+pub mod transaction;
+pub mod patch;
+pub mod history;
+pub mod case_path;
+pub mod reducer;
+
+// Deprecated aliases kept for source compatibility.
+pub mod combinig;
+pub mod keypaths;
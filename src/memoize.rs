@@ -0,0 +1,288 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::clock::Clock;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use crate::clock::StdClock;
+
+// Caching wrappers for pure functions. The cache lives behind a `Mutex`, so the
+// memoized function is `Send + Sync` whenever the wrapped function is.
+// The lock is never held while the wrapped function runs.
+
+/// Cache every result forever.
+pub fn memoize<A, B, F>(f: F) -> impl Fn(A) -> B
+where
+    F: Fn(A) -> B,
+    A: Eq + Hash + Clone,
+    B: Clone,
+{
+    let cache: Mutex<HashMap<A, B>> = Mutex::new(HashMap::new());
+    move |a: A| {
+        if let Some(b) = lock(&cache).get(&a) {
+            return b.clone();
+        }
+        let b = f(a.clone());
+        lock(&cache).insert(a, b.clone());
+        b
+    }
+}
+
+/// Keep at most `capacity` results, evicting the least recently used one.
+/// A capacity of zero disables caching.
+pub fn memoize_lru<A, B, F>(f: F, capacity: usize) -> impl Fn(A) -> B
+where
+    F: Fn(A) -> B,
+    A: Eq + Hash + Clone,
+    B: Clone,
+{
+    let cache = Mutex::new(LruCache::new(capacity));
+    move |a: A| {
+        if let Some(b) = lock(&cache).get(&a) {
+            return b;
+        }
+        let b = f(a.clone());
+        lock(&cache).insert(a, b.clone());
+        b
+    }
+}
+
+/// Reuse a result only while it is younger than `ttl`; stale entries are recomputed.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn memoize_ttl<A, B, F>(f: F, ttl: Duration) -> impl Fn(A) -> B
+where
+    F: Fn(A) -> B,
+    A: Eq + Hash + Clone,
+    B: Clone,
+{
+    memoize_ttl_with_clock(f, ttl, StdClock)
+}
+
+/// `memoize_ttl`, reading entry ages from `clock`.
+pub fn memoize_ttl_with_clock<A, B, F, C>(f: F, ttl: Duration, clock: C) -> impl Fn(A) -> B
+where
+    F: Fn(A) -> B,
+    A: Eq + Hash + Clone,
+    B: Clone,
+    C: Clock,
+{
+    let cache = Mutex::new(TtlCache::new(ttl));
+    move |a: A| {
+        if let Some(b) = lock(&cache).get(&a, clock.now()) {
+            return b;
+        }
+        let b = f(a.clone());
+        lock(&cache).insert(a, b.clone(), clock.now());
+        b
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    // A panic inside `f` never happens while the lock is held, so the data is consistent.
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Least-recently-used cache. `recency` orders the keys by their last access tick,
+/// so a lookup or an eviction costs O(log n).
+struct LruCache<A, B> {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<A, (B, u64)>,
+    recency: BTreeMap<u64, A>,
+}
+
+impl<A: Eq + Hash + Clone, B: Clone> LruCache<A, B> {
+    fn new(capacity: usize) -> Self {
+        Self { capacity, tick: 0, entries: HashMap::new(), recency: BTreeMap::new() }
+    }
+
+    fn get(&mut self, a: &A) -> Option<B> {
+        let (b, last_used) = self.entries.get_mut(a)?;
+        self.tick += 1;
+        let key = self.recency.remove(last_used)?;
+        *last_used = self.tick;
+        self.recency.insert(self.tick, key);
+        Some(b.clone())
+    }
+
+    fn insert(&mut self, a: A, b: B) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some((_, last_used)) = self.entries.get(&a) {
+            self.recency.remove(last_used);
+        } else if self.entries.len() >= self.capacity
+            && let Some((_, oldest)) = self.recency.pop_first()
+        {
+            self.entries.remove(&oldest);
+        }
+        self.tick += 1;
+        self.recency.insert(self.tick, a.clone());
+        self.entries.insert(a, (b, self.tick));
+    }
+}
+
+/// Cache whose entries expire `ttl` after they were stored. A stale entry is dropped
+/// when it is looked up. `stored` lists insertions oldest first (the clock never goes
+/// back), so expired entries that are never looked up again are dropped from its
+/// front on insertion, in amortized O(1).
+struct TtlCache<A, B> {
+    ttl: Duration,
+    entries: HashMap<A, (B, Duration)>,
+    stored: VecDeque<(Duration, A)>,
+}
+
+impl<A: Eq + Hash + Clone, B: Clone> TtlCache<A, B> {
+    fn new(ttl: Duration) -> Self {
+        Self { ttl, entries: HashMap::new(), stored: VecDeque::new() }
+    }
+
+    fn get(&mut self, a: &A, now: Duration) -> Option<B> {
+        let (b, stored_at) = self.entries.get(a)?;
+        if now.saturating_sub(*stored_at) < self.ttl {
+            return Some(b.clone());
+        }
+        self.entries.remove(a);
+        None
+    }
+
+    fn insert(&mut self, a: A, b: B, now: Duration) {
+        while let Some((stored_at, _)) = self.stored.front()
+            && now.saturating_sub(*stored_at) >= self.ttl
+        {
+            let (stored_at, key) = self.stored.pop_front().expect("front was just checked");
+            // Only drop the entry if it has not been refreshed since this record.
+            if self.entries.get(&key).is_some_and(|(_, at)| *at == stored_at) {
+                self.entries.remove(&key);
+            }
+        }
+        self.stored.push_back((now, a.clone()));
+        self.entries.insert(a, (b, now));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_memoize() {
+        let calls = AtomicUsize::new(0);
+        let square = memoize(|x: u64| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            x * x
+        });
+        assert_eq!(square(4), 16);
+        assert_eq!(square(4), 16);
+        assert_eq!(square(5), 25);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_memoize_lru_evicts_least_recently_used() {
+        let calls = AtomicUsize::new(0);
+        let f = memoize_lru(
+            |x: i32| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                x + 100
+            },
+            2,
+        );
+        f(1);
+        f(2);
+        f(1); // 1 is now more recent than 2
+        f(3); // evicts 2
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        f(1);
+        assert_eq!(calls.load(Ordering::SeqCst), 3, "1 should still be cached");
+        assert_eq!(f(2), 102);
+        assert_eq!(calls.load(Ordering::SeqCst), 4, "2 should have been evicted");
+    }
+
+    #[test]
+    fn test_memoize_lru_zero_capacity() {
+        let calls = AtomicUsize::new(0);
+        let f = memoize_lru(
+            |x: i32| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                x
+            },
+            0,
+        );
+        f(1);
+        f(1);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_memoize_ttl_fresh_entries_are_reused() {
+        let calls = AtomicUsize::new(0);
+        let f = memoize_ttl(
+            |s: String| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                s.len()
+            },
+            Duration::from_secs(60),
+        );
+        assert_eq!(f("abc".to_string()), 3);
+        assert_eq!(f("abc".to_string()), 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_memoize_ttl_stale_entries_are_recomputed() {
+        let calls = AtomicUsize::new(0);
+        let f = memoize_ttl(
+            |x: i32| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                x
+            },
+            Duration::ZERO,
+        );
+        f(1);
+        f(1);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_memoize_ttl_with_manual_clock() {
+        let clock = crate::clock::ManualClock::default();
+        let calls = AtomicUsize::new(0);
+        let f = memoize_ttl_with_clock(
+            |x: i32| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                x
+            },
+            Duration::from_secs(10),
+            clock.clone(),
+        );
+        f(1);
+        clock.advance(Duration::from_secs(9));
+        f(1);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        clock.advance(Duration::from_secs(1));
+        f(1);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_ttl_cache_drops_expired_entries_on_insert() {
+        let clock = crate::clock::ManualClock::default();
+        let mut cache = TtlCache::new(Duration::from_secs(10));
+        cache.insert("a", 1, clock.now());
+        clock.advance(Duration::from_secs(5));
+        cache.insert("b", 2, clock.now());
+        cache.insert("a", 3, clock.now());
+        clock.advance(Duration::from_secs(6));
+        cache.insert("c", 4, clock.now());
+        // The first record of "a" expired, but "a" was refreshed and is kept.
+        assert_eq!(cache.entries.len(), 3);
+        clock.advance(Duration::from_secs(5));
+        cache.insert("d", 5, clock.now());
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.get(&"c", clock.now()), Some(4));
+        assert_eq!(cache.get(&"a", clock.now()), None);
+    }
+}
@@ -0,0 +1,175 @@
+use core::ops::{Add, Mul};
+
+use crate::prelude::*;
+use crate::non_empty::NonEmpty;
+
+/// Types with an associative way of combining two values.
+pub trait Semigroup {
+    fn combine(self, other: Self) -> Self;
+}
+
+/// A semigroup with an identity element: `empty().combine(a) == a`.
+pub trait Monoid: Semigroup {
+    fn empty() -> Self;
+}
+
+/// Combine everything, starting from `empty()`.
+pub fn mconcat<M: Monoid>(items: impl IntoIterator<Item = M>) -> M {
+    items.into_iter().fold(M::empty(), M::combine)
+}
+
+/// Combine a non-empty collection; no identity element is needed.
+pub fn sconcat<S: Semigroup>(items: NonEmpty<S>) -> S {
+    items.reduce(S::combine)
+}
+
+impl Semigroup for String {
+    fn combine(mut self, other: Self) -> Self {
+        self.push_str(&other);
+        self
+    }
+}
+
+impl Monoid for String {
+    fn empty() -> Self {
+        String::new()
+    }
+}
+
+impl<T> Semigroup for Vec<T> {
+    fn combine(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl<T> Monoid for Vec<T> {
+    fn empty() -> Self {
+        Vec::new()
+    }
+}
+
+/// `Some` values are combined; `None` is the identity.
+impl<T: Semigroup> Semigroup for Option<T> {
+    fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.combine(b)),
+            (a, None) => a,
+            (None, b) => b,
+        }
+    }
+}
+
+impl<T: Semigroup> Monoid for Option<T> {
+    fn empty() -> Self {
+        None
+    }
+}
+
+/// Numbers combined by addition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Sum<T>(pub T);
+
+impl<T: Add<Output = T>> Semigroup for Sum<T> {
+    fn combine(self, other: Self) -> Self {
+        Sum(self.0 + other.0)
+    }
+}
+
+/// Numbers combined by multiplication.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Product<T>(pub T);
+
+impl<T: Mul<Output = T>> Semigroup for Product<T> {
+    fn combine(self, other: Self) -> Self {
+        Product(self.0 * other.0)
+    }
+}
+
+macro_rules! numeric_monoids {
+    ($($t:ty => $zero:expr, $one:expr);+ $(;)?) => {
+        $(
+            impl Monoid for Sum<$t> {
+                fn empty() -> Self {
+                    Sum($zero)
+                }
+            }
+
+            impl Monoid for Product<$t> {
+                fn empty() -> Self {
+                    Product($one)
+                }
+            }
+        )+
+    };
+}
+
+numeric_monoids!(
+    i8 => 0, 1; i16 => 0, 1; i32 => 0, 1; i64 => 0, 1; i128 => 0, 1; isize => 0, 1;
+    u8 => 0, 1; u16 => 0, 1; u32 => 0, 1; u64 => 0, 1; u128 => 0, 1; usize => 0, 1;
+    f32 => 0.0, 1.0; f64 => 0.0, 1.0;
+);
+
+/// Functions `(A) -> A` combined by composition, left to right: `f.combine(g)` runs `f` then `g`.
+pub struct Endo<A>(pub Box<dyn Fn(A) -> A>);
+
+impl<A: 'static> Endo<A> {
+    pub fn new(f: impl Fn(A) -> A + 'static) -> Self {
+        Endo(Box::new(f))
+    }
+
+    pub fn call(&self, a: A) -> A {
+        (self.0)(a)
+    }
+}
+
+impl<A: 'static> Semigroup for Endo<A> {
+    fn combine(self, other: Self) -> Self {
+        let (f, g) = (self.0, other.0);
+        Endo(Box::new(move |a| g(f(a))))
+    }
+}
+
+impl<A: 'static> Monoid for Endo<A> {
+    fn empty() -> Self {
+        Endo(Box::new(|a| a))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mconcat_strings_and_vecs() {
+        assert_eq!(mconcat(vec!["ab".to_string(), "c".to_string()]), "abc");
+        assert_eq!(mconcat(vec![vec![1], vec![], vec![2, 3]]), vec![1, 2, 3]);
+        assert_eq!(mconcat(Vec::<String>::new()), "");
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        assert_eq!(mconcat([1, 2, 3, 4].map(Sum)), Sum(10));
+        assert_eq!(mconcat([1, 2, 3, 4].map(Product)), Product(24));
+        assert_eq!(mconcat(Vec::<Product<f64>>::new()), Product(1.0));
+    }
+
+    #[test]
+    fn test_option_skips_none() {
+        let errors = vec![Some(vec!["a"]), None, Some(vec!["b"])];
+        assert_eq!(mconcat(errors), Some(vec!["a", "b"]));
+        assert_eq!(mconcat(vec![None::<String>, None]), None);
+    }
+
+    #[test]
+    fn test_endo_composes_left_to_right() {
+        let f = mconcat(vec![Endo::new(|x: i32| x + 1), Endo::new(|x| x * 2), Endo::empty()]);
+        assert_eq!(f.call(3), 8);
+    }
+
+    #[test]
+    fn test_sconcat() {
+        let ne = NonEmpty::new(Sum(1), vec![Sum(2)]);
+        assert_eq!(sconcat(ne), Sum(3));
+    }
+}
@@ -0,0 +1,281 @@
+// Functions that carry a name, so composed pipelines can list their stages and say which
+// stage failed instead of being an anonymous closure chain.
+
+use core::fmt;
+
+use crate::prelude::*;
+use crate::conditional::Branch;
+use crate::result::Contextual;
+
+/// A function with a debug name.
+#[derive(Clone, Copy)]
+pub struct Named<F> {
+    name: &'static str,
+    f: F,
+}
+
+pub fn named<F>(name: &'static str, f: F) -> Named<F> {
+    Named { name, f }
+}
+
+impl<F> Named<F> {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn call<A, B>(&self, a: A) -> B
+    where
+        F: Fn(A) -> B,
+    {
+        (self.f)(a)
+    }
+
+    pub fn into_fn(self) -> F {
+        self.f
+    }
+}
+
+impl<F> fmt::Debug for Named<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Named").field(&self.name).finish()
+    }
+}
+
+// The shape of a pipeline, kept for `stages` and `to_dot`.
+#[derive(Debug, Clone)]
+enum Node {
+    Stage(&'static str),
+    Cond { name: &'static str, arms: Vec<&'static str> },
+    Fanout { name: &'static str, arms: Vec<&'static str> },
+}
+
+impl Node {
+    fn name(&self) -> &'static str {
+        match self {
+            Node::Stage(name) | Node::Cond { name, .. } | Node::Fanout { name, .. } => name,
+        }
+    }
+}
+
+/// A fallible step stored in a list, e.g. an arm of [`NamedPipeline::fanout`].
+pub type Stage<B, C, E> = Box<dyn Fn(B) -> Result<C, E>>;
+
+/// A throwing pipeline of named stages. Errors are reported as `Contextual` with the
+/// name of the stage that failed as the context; inside `cond` and `fanout` the context
+/// is `"branch point/arm"`.
+pub struct NamedPipeline<A, B, E> {
+    stages: Vec<Node>,
+    f: Box<dyn Fn(A) -> Result<B, Contextual<E>>>,
+}
+
+impl<A: 'static, E: 'static> NamedPipeline<A, A, E> {
+    /// An empty pipeline, returning its input.
+    pub fn new() -> Self {
+        Self { stages: Vec::new(), f: Box::new(Ok) }
+    }
+}
+
+impl<A: 'static, E: 'static> Default for NamedPipeline<A, A, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: 'static, B: 'static, E: 'static> NamedPipeline<A, B, E> {
+    /// Append a fallible stage; its error converts `Into` the pipeline's `E`.
+    pub fn then<C, E2, G>(self, stage: Named<G>) -> NamedPipeline<A, C, E>
+    where
+        G: Fn(B) -> Result<C, E2> + 'static,
+        E2: Into<E>,
+    {
+        let (f, Named { name, f: g }) = (self.f, stage);
+        let mut stages = self.stages;
+        stages.push(Node::Stage(name));
+        NamedPipeline {
+            stages,
+            f: Box::new(move |a: A| g(f(a)?).map_err(|e| Contextual { context: name.to_string(), error: e.into() })),
+        }
+    }
+
+    /// `then(named(name, f))`.
+    pub fn stage<C, E2, G>(self, name: &'static str, f: G) -> NamedPipeline<A, C, E>
+    where
+        G: Fn(B) -> Result<C, E2> + 'static,
+        E2: Into<E>,
+    {
+        self.then(named(name, f))
+    }
+
+    /// Append a stage that cannot fail.
+    pub fn map<C: 'static>(self, name: &'static str, f: impl Fn(B) -> C + 'static) -> NamedPipeline<A, C, E> {
+        self.stage(name, move |b: B| Ok::<C, E>(f(b)))
+    }
+
+    /// Branch point: run the first arm whose condition holds, or `default`.
+    pub fn cond<C: 'static, G>(self, name: &'static str, arms: Vec<Named<Branch<'static, B, Result<C, E>>>>, default: Named<G>) -> NamedPipeline<A, C, E>
+    where
+        G: Fn(B) -> Result<C, E> + 'static,
+    {
+        let mut stages = self.stages;
+        let mut arm_names: Vec<&'static str> = arms.iter().map(Named::name).collect();
+        arm_names.push(default.name());
+        stages.push(Node::Cond { name, arms: arm_names });
+        let (f, default_name, default) = (self.f, default.name(), default.into_fn());
+        NamedPipeline {
+            stages,
+            f: Box::new(move |a: A| {
+                let b = f(a)?;
+                let (arm, result) = match arms.iter().find(|arm| (arm.f.0)(&b)) {
+                    Some(arm) => (arm.name, (arm.f.1)(b)),
+                    None => (default_name, default(b)),
+                };
+                result.map_err(|error| Contextual { context: format!("{name}/{arm}"), error })
+            }),
+        }
+    }
+
+    /// Branch point: run every arm on a copy of the value, stopping at the first error.
+    pub fn fanout<C: 'static>(self, name: &'static str, arms: Vec<Named<Stage<B, C, E>>>) -> NamedPipeline<A, Vec<C>, E>
+    where
+        B: Clone,
+    {
+        let mut stages = self.stages;
+        stages.push(Node::Fanout { name, arms: arms.iter().map(Named::name).collect() });
+        let f = self.f;
+        NamedPipeline {
+            stages,
+            f: Box::new(move |a: A| {
+                let b = f(a)?;
+                arms.iter()
+                    .map(|arm| (arm.f)(b.clone()).map_err(|error| Contextual { context: format!("{name}/{}", arm.name), error }))
+                    .collect()
+            }),
+        }
+    }
+
+    /// The stage names in the order they run; a branch point is listed by its own name.
+    pub fn stages(&self) -> Vec<&str> {
+        self.stages.iter().map(Node::name).collect()
+    }
+
+    /// The pipeline as a Graphviz DOT graph: one node per stage, with the arms of
+    /// `cond` (diamond) and `fanout` (trapezium) branch points joining again afterwards.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph pipeline {\n    rankdir=LR;\n    input [shape=circle];\n");
+        let mut previous = vec!["input".to_string()];
+        for (i, node) in self.stages.iter().enumerate() {
+            let id = format!("s{i}");
+            let (shape, arms) = match node {
+                Node::Stage(_) => ("box", None),
+                Node::Cond { arms, .. } => ("diamond", Some(arms)),
+                Node::Fanout { arms, .. } => ("trapezium", Some(arms)),
+            };
+            dot.push_str(&format!("    {id} [label={:?}, shape={shape}];\n", node.name()));
+            for from in &previous {
+                dot.push_str(&format!("    {from} -> {id};\n"));
+            }
+            previous = match arms {
+                None => vec![id],
+                Some(arms) => arms
+                    .iter()
+                    .enumerate()
+                    .map(|(j, arm)| {
+                        let arm_id = format!("{id}_{j}");
+                        dot.push_str(&format!("    {arm_id} [label={arm:?}, shape=box];\n    {id} -> {arm_id};\n"));
+                        arm_id
+                    })
+                    .collect(),
+            };
+        }
+        dot.push_str("    output [shape=doublecircle];\n");
+        for from in &previous {
+            dot.push_str(&format!("    {from} -> output;\n"));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn run(&self, a: A) -> Result<B, Contextual<E>> {
+        (self.f)(a)
+    }
+}
+
+impl<A, B, E> fmt::Debug for NamedPipeline<A, B, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stages: Vec<&str> = self.stages.iter().map(Node::name).collect();
+        f.debug_struct("NamedPipeline").field("stages", &stages).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipeline() -> NamedPipeline<String, u32, String> {
+        NamedPipeline::new()
+            .map("trim", |s: String| s.trim().to_string())
+            .stage("parse", |s: String| s.parse::<u32>().map_err(|e| e.to_string()))
+            .then(named("cap", |n: u32| if n <= 100 { Ok(n) } else { Err(format!("{n} > 100")) }))
+    }
+
+    #[test]
+    fn test_stages_and_failing_stage_name() {
+        let p = pipeline();
+        assert_eq!(p.stages(), vec!["trim", "parse", "cap"]);
+        assert_eq!(p.run(" 42 ".to_string()), Ok(42));
+        let err = p.run("500".to_string()).unwrap_err();
+        assert_eq!(err.context, "cap");
+        assert_eq!(err.to_string(), "cap: 500 > 100");
+        assert_eq!(p.run("x".to_string()).unwrap_err().context, "parse");
+        assert_eq!(format!("{p:?}"), r#"NamedPipeline { stages: ["trim", "parse", "cap"] }"#);
+    }
+
+    #[test]
+    fn test_named() {
+        let double = named("double", |n: i32| n * 2);
+        assert_eq!(double.call(4), 8);
+        assert_eq!(format!("{double:?}"), r#"Named("double")"#);
+        assert_eq!([1, 2].map(double.into_fn()), [2, 4]);
+    }
+
+    fn routed() -> NamedPipeline<u32, Vec<String>, String> {
+        NamedPipeline::new()
+            .stage("check", |n: u32| if n > 0 { Ok(n) } else { Err("zero amount".to_string()) })
+            .cond(
+                "route",
+                vec![named("instant", crate::conditional::branch(|n: &u32| *n < 100, |n: u32| Ok(format!("instant {n}"))))],
+                named("batch", |n: u32| if n < 10_000 { Ok(format!("batch {n}")) } else { Err(format!("{n} needs approval")) }),
+            )
+            .fanout(
+                "notify",
+                vec![
+                    named("email", Box::new(|s: String| Ok(format!("email: {s}"))) as Stage<String, String, String>),
+                    named("audit", Box::new(|s: String| Ok(format!("audit: {s}")))),
+                ],
+            )
+    }
+
+    #[test]
+    fn test_cond_and_fanout() {
+        let p = routed();
+        assert_eq!(p.stages(), vec!["check", "route", "notify"]);
+        assert_eq!(p.run(5), Ok(vec!["email: instant 5".to_string(), "audit: instant 5".to_string()]));
+        assert_eq!(p.run(500).unwrap()[0], "email: batch 500");
+        assert_eq!(p.run(20_000).unwrap_err().to_string(), "route/batch: 20000 needs approval");
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = routed().to_dot();
+        assert!(dot.starts_with("digraph pipeline {\n"));
+        assert!(dot.contains("    s1 [label=\"route\", shape=diamond];\n"));
+        assert!(dot.contains("    s1 -> s1_1;\n") && dot.contains("    s1_0 -> s2;\n    s1_1 -> s2;\n"));
+        assert!(dot.contains("    s2_0 -> output;\n    s2_1 -> output;\n}\n"));
+        let simple = NamedPipeline::<u8, u8, ()>::new().map("id", |n| n).to_dot();
+        assert_eq!(
+            simple,
+            "digraph pipeline {\n    rankdir=LR;\n    input [shape=circle];\n    s0 [label=\"id\", shape=box];\n    input -> s0;\n    \
+             output [shape=doublecircle];\n    s0 -> output;\n}\n"
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::prelude::*;
+
+/// A vector with at least one element, so `first`, `last` and `reduce` need no `Option`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NonEmpty<T> {
+    head: T,
+    tail: Vec<T>,
+}
+
+// `len` is always at least 1, so an `is_empty` would be meaningless.
+#[allow(clippy::len_without_is_empty)]
+impl<T> NonEmpty<T> {
+    pub fn new(head: T, tail: Vec<T>) -> Self {
+        Self { head, tail }
+    }
+
+    pub fn singleton(head: T) -> Self {
+        Self { head, tail: Vec::new() }
+    }
+
+    /// `None` for an empty vector.
+    pub fn from_vec(mut v: Vec<T>) -> Option<Self> {
+        if v.is_empty() {
+            return None;
+        }
+        let head = v.remove(0);
+        Some(Self { head, tail: v })
+    }
+
+    pub fn first(&self) -> &T {
+        &self.head
+    }
+
+    pub fn last(&self) -> &T {
+        self.tail.last().unwrap_or(&self.head)
+    }
+
+    pub fn len(&self) -> usize {
+        1 + self.tail.len()
+    }
+
+    pub fn push(&mut self, t: T) {
+        self.tail.push(t);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        core::iter::once(&self.head).chain(self.tail.iter())
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> NonEmpty<U> {
+        let head = f(self.head);
+        NonEmpty { head, tail: self.tail.into_iter().map(f).collect() }
+    }
+
+    /// Fold the elements left to right starting from the first one.
+    pub fn reduce(self, f: impl FnMut(T, T) -> T) -> T {
+        self.tail.into_iter().fold(self.head, f)
+    }
+
+    /// Pair elements up, stopping at the shorter of the two.
+    pub fn zip<U>(self, other: NonEmpty<U>) -> NonEmpty<(T, U)> {
+        NonEmpty {
+            head: (self.head, other.head),
+            tail: self.tail.into_iter().zip(other.tail).collect(),
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        let mut v = Vec::with_capacity(self.len());
+        v.push(self.head);
+        v.extend(self.tail);
+        v
+    }
+}
+
+impl<T> From<NonEmpty<T>> for Vec<T> {
+    fn from(ne: NonEmpty<T>) -> Self {
+        ne.into_vec()
+    }
+}
+
+impl<T> IntoIterator for NonEmpty<T> {
+    type Item = T;
+    type IntoIter = core::iter::Chain<core::iter::Once<T>, alloc::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        core::iter::once(self.head).chain(self.tail)
+    }
+}
+
+// zipN over non-empty vectors: the result is as long as the shortest input, and so
+// never empty.
+macro_rules! zip_non_empty {
+    ($name:ident, $($v:ident: $T:ident),+) => {
+        #[allow(clippy::too_many_arguments)]
+        pub fn $name<$($T),+>($($v: NonEmpty<$T>),+) -> NonEmpty<($($T,)+)> {
+            let head = ($($v.head,)+);
+            $(let mut $v = $v.tail.into_iter();)+
+            let mut tail = Vec::new();
+            loop {
+                tail.push(($(match $v.next() {
+                    Some(x) => x,
+                    None => break,
+                },)+));
+            }
+            NonEmpty { head, tail }
+        }
+    };
+}
+
+arities!(zip_non_empty; _, _, zip3, zip4, zip5, zip6, zip7, zip8, zip9, zip10, zip11, zip12, zip13, zip14, zip15, zip16);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_vec() {
+        assert_eq!(NonEmpty::<i32>::from_vec(vec![]), None);
+        let ne = NonEmpty::from_vec(vec![1, 2, 3]).unwrap();
+        assert_eq!(ne.first(), &1);
+        assert_eq!(ne.last(), &3);
+        assert_eq!(ne.len(), 3);
+    }
+
+    #[test]
+    fn test_singleton_last_is_head() {
+        let ne = NonEmpty::singleton("only");
+        assert_eq!(ne.last(), &"only");
+        assert_eq!(ne.into_vec(), vec!["only"]);
+    }
+
+    #[test]
+    fn test_map_and_reduce() {
+        let ne = NonEmpty::new(1, vec![2, 3, 4]);
+        assert_eq!(ne.clone().map(|x| x * 10).into_vec(), vec![10, 20, 30, 40]);
+        assert_eq!(ne.reduce(|a, b| a + b), 10);
+        assert_eq!(NonEmpty::singleton(7).reduce(|a, b| a * b), 7);
+    }
+
+    #[test]
+    fn test_zip_truncates_to_shortest() {
+        let a = NonEmpty::new(1, vec![2, 3]);
+        let b = NonEmpty::singleton("x");
+        assert_eq!(a.zip(b).into_vec(), vec![(1, "x")]);
+    }
+
+    #[test]
+    fn test_zip3() {
+        let zipped = zip3(
+            NonEmpty::new(1, vec![2]),
+            NonEmpty::new('a', vec!['b', 'c']),
+            NonEmpty::new(true, vec![false]),
+        );
+        assert_eq!(zipped.into_vec(), vec![(1, 'a', true), (2, 'b', false)]);
+    }
+
+    #[test]
+    fn test_zip10() {
+        let n = |x: i32| NonEmpty::new(x, vec![x + 1]);
+        let zipped = zip10(n(0), n(10), n(20), n(30), n(40), n(50), n(60), n(70), n(80), n(90));
+        assert_eq!(zipped.len(), 2);
+        assert_eq!(zipped.last(), &(1, 11, 21, 31, 41, 51, 61, 71, 81, 91));
+    }
+
+    #[test]
+    fn test_into_iter_and_iter() {
+        let ne = NonEmpty::new("a".to_string(), vec!["b".to_string()]);
+        let lens: Vec<usize> = ne.iter().map(String::len).collect();
+        assert_eq!(lens, vec![1, 1]);
+        let joined: String = ne.into_iter().collect();
+        assert_eq!(joined, "ab");
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// Run `f` on the first call only; every later call returns a copy of that first output,
+/// whatever its argument. Concurrent first calls wait for the one that runs `f`.
+pub fn once<A, B, F>(f: F) -> impl Fn(A) -> B
+where
+    F: Fn(A) -> B,
+    B: Clone,
+{
+    let output = OnceLock::new();
+    move |a: A| output.get_or_init(|| f(a)).clone()
+}
+
+/// Like `once`, but only a successful output is cached: after an `Err` the next call
+/// tries again.
+pub fn once_fallible<A, B, E, F>(f: F) -> impl Fn(A) -> Result<B, E>
+where
+    F: Fn(A) -> Result<B, E>,
+    B: Clone,
+{
+    let output: Mutex<Option<B>> = Mutex::new(None);
+    move |a: A| {
+        // The lock is held while `f` runs so that at most one call can succeed.
+        let mut output = output.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(b) = output.as_ref() {
+            return Ok(b.clone());
+        }
+        let b = f(a)?;
+        *output = Some(b.clone());
+        Ok(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_once_runs_only_first_time() {
+        let calls = AtomicUsize::new(0);
+        let init = once(|x: i32| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            x * 10
+        });
+        assert_eq!(init(1), 10);
+        assert_eq!(init(2), 10);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_once_across_threads() {
+        let calls = AtomicUsize::new(0);
+        let init = once(|_: ()| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            "config".to_string()
+        });
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| assert_eq!(init(()), "config"));
+            }
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_once_fallible_retries_after_error() {
+        let calls = AtomicUsize::new(0);
+        let connect = once_fallible(|addr: &str| {
+            let n = calls.fetch_add(1, Ordering::SeqCst);
+            if n == 0 { Err("refused") } else { Ok(format!("connected to {}", addr)) }
+        });
+        assert_eq!(connect("a"), Err("refused"));
+        assert_eq!(connect("b"), Ok("connected to b".to_string()));
+        assert_eq!(connect("c"), Ok("connected to b".to_string()));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,217 @@
+// Helpers for optional values.
+
+use crate::functor;
+use crate::prelude::*;
+use crate::uncurry::CurriedStep;
+
+/// Turn a collection of options into an option of a collection: `None` if any element is `None`.
+pub fn sequence<T>(options: impl IntoIterator<Item = Option<T>>) -> Option<Vec<T>> {
+    options.into_iter().collect()
+}
+
+/// Run a partial function over every element, `None` as soon as one element has no result.
+pub fn traverse<A, B, F>(items: impl IntoIterator<Item = A>, f: F) -> Option<Vec<B>>
+where
+    F: FnMut(A) -> Option<B>,
+{
+    items.into_iter().map(f).collect()
+}
+
+/// The first `Some`, e.g. `coalesce([from_cli, from_env, from_file])`.
+pub fn coalesce<T>(options: impl IntoIterator<Item = Option<T>>) -> Option<T> {
+    options.into_iter().flatten().next()
+}
+
+/// Like [`coalesce`], but each candidate is computed by a closure that only runs while no
+/// earlier one produced a value.
+pub fn first_some<T, F>(thunks: impl IntoIterator<Item = F>) -> Option<T>
+where
+    F: FnOnce() -> Option<T>,
+{
+    thunks.into_iter().find_map(|f| f())
+}
+
+/// End a fallback chain: unwrap, computing the default only for `None`.
+pub fn or_default_with<T, F>(f: F) -> impl Fn(Option<T>) -> T
+where
+    F: Fn() -> T,
+{
+    move |o: Option<T>| o.unwrap_or_else(&f)
+}
+
+// liftN: turn an ordinary `(A, B, ...) -> R` into one over options, `None` if any argument is `None`.
+macro_rules! lift_option {
+    ($name:ident, $($a:ident: $A:ident),+) => {
+        pub fn $name<$($A,)+ R, F>(f: F) -> impl Fn($(Option<$A>),+) -> Option<R>
+        where
+            F: Fn($($A),+) -> R,
+        {
+            move |$($a: Option<$A>),+| Some(f($($a?),+))
+        }
+    };
+}
+
+/// `lift2` is the generic applicative one from `functor`, specialised to `Option`.
+pub fn lift2<A, B, R, F>(f: F) -> impl Fn(Option<A>, Option<B>) -> Option<R>
+where
+    F: Fn(A, B) -> R,
+{
+    functor::lift2(f)
+}
+
+lift_option!(lift3, a: A, b: B, c: C);
+lift_option!(lift4, a: A, b: B, c: C, d: D);
+lift_option!(lift5, a: A, b: B, c: C, d: D, e: E);
+lift_option!(lift6, a: A, b: B, c: C, d: D, e: E, g: G);
+lift_option!(lift7, a: A, b: B, c: C, d: D, e: E, g: G, h: H);
+lift_option!(lift8, a: A, b: B, c: C, d: D, e: E, g: G, h: H, i: I);
+lift_option!(lift9, a: A, b: B, c: C, d: D, e: E, g: G, h: H, i: I, j: J);
+lift_option!(lift10, a: A, b: B, c: C, d: D, e: E, g: G, h: H, i: I, j: J, k: K);
+
+// zipN: pair up options, `None` if any of them is `None`.
+macro_rules! zip_option {
+    ($name:ident, $($a:ident: $A:ident),+) => {
+        #[allow(clippy::too_many_arguments)]
+        pub fn $name<$($A),+>($($a: Option<$A>),+) -> Option<($($A,)+)> {
+            Some(($($a?,)+))
+        }
+    };
+}
+
+arities!(zip_option; _, zip2, zip3, zip4, zip5, zip6, zip7, zip8, zip9, zip10, zip11, zip12, zip13, zip14, zip15, zip16);
+
+// zipN_lazy: like zipN, but each argument is computed by a closure that only runs if
+// every earlier one produced `Some`, so expensive lookups are skipped after a miss.
+macro_rules! zip_option_lazy {
+    ($name:ident, $($a:ident: $A:ident),+) => {
+        #[allow(clippy::too_many_arguments)]
+        pub fn $name<$($A),+>($($a: impl FnOnce() -> Option<$A>),+) -> Option<($($A,)+)> {
+            Some(($($a()?,)+))
+        }
+    };
+}
+
+arities!(zip_option_lazy; _, zip2_lazy, zip3_lazy, zip4_lazy, zip5_lazy, zip6_lazy, zip7_lazy, zip8_lazy, zip9_lazy, zip10_lazy, zip11_lazy, zip12_lazy, zip13_lazy, zip14_lazy, zip15_lazy, zip16_lazy);
+
+/// Applicative `apply`: call a wrapped function with a wrapped argument, `None` if either is missing.
+/// Works with plain closures and with the curried functions of the curry module, so
+/// `Some(curry2(f)).apply(a).apply(b)` feeds optional arguments one at a time.
+pub fn apply<A, F>(f: Option<F>, a: Option<A>) -> Option<F::Output>
+where
+    F: CurriedStep<A>,
+{
+    Some(f?.apply(a?))
+}
+
+/// Method form of [`apply`].
+pub trait Apply<A> {
+    type Output;
+
+    fn apply(self, a: Option<A>) -> Option<Self::Output>;
+}
+
+impl<A, F> Apply<A> for Option<F>
+where
+    F: CurriedStep<A>,
+{
+    type Output = F::Output;
+
+    fn apply(self, a: Option<A>) -> Option<F::Output> {
+        apply(self, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curry::curry2;
+
+    #[test]
+    fn test_sequence() {
+        assert_eq!(sequence(vec![Some(1), Some(2)]), Some(vec![1, 2]));
+        assert_eq!(sequence(vec![Some(1), None]), None);
+        assert_eq!(sequence(Vec::<Option<i32>>::new()), Some(vec![]));
+    }
+
+    #[test]
+    fn test_traverse() {
+        let first_char = |s: &str| s.chars().next();
+        assert_eq!(traverse(["ab", "c"], first_char), Some(vec!['a', 'c']));
+        assert_eq!(traverse(["ab", ""], first_char), None);
+    }
+
+    #[test]
+    fn test_lift2() {
+        let add = lift2(|a: i32, b: i32| a + b);
+        assert_eq!(add(Some(1), Some(2)), Some(3));
+        assert_eq!(add(Some(1), None), None);
+    }
+
+    #[test]
+    fn test_lift3_with_lookups() {
+        let rates = [("EUR", 1.1), ("GBP", 1.3)];
+        let rate = |code: &str| rates.iter().find(|(c, _)| *c == code).map(|(_, r)| *r);
+        let convert = lift3(|amount: f64, from: f64, to: f64| amount * from / to);
+        assert_eq!(convert(Some(13.0), rate("EUR"), rate("GBP")), Some(11.0));
+        assert_eq!(convert(Some(13.0), rate("EUR"), rate("JPY")), None);
+    }
+
+    #[test]
+    fn test_lift10() {
+        let sum = lift10(|a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8, i: u8, j: u8| {
+            a + b + c + d + e + f + g + h + i + j
+        });
+        let s = Some(1);
+        assert_eq!(sum(s, s, s, s, s, s, s, s, s, s), Some(10));
+        assert_eq!(sum(s, s, s, s, s, s, s, s, s, None), None);
+    }
+
+    #[test]
+    fn test_apply_with_curried_function() {
+        let add = curry2(|a: i32, b: i32| a + b);
+        assert_eq!(Some(&add).apply(Some(1)).apply(Some(2)), Some(3));
+        assert_eq!(Some(&add).apply(None).apply(Some(2)), None);
+    }
+
+    #[test]
+    fn test_apply_free_function() {
+        assert_eq!(apply(Some(|s: &str| s.len()), Some("abc")), Some(3));
+        assert_eq!(apply(None::<fn(&str) -> usize>, Some("abc")), None);
+    }
+
+    #[test]
+    fn test_zip() {
+        assert_eq!(zip2(Some(1), Some("a")), Some((1, "a")));
+        assert_eq!(zip3(Some(1), None::<u8>, Some('c')), None);
+        let z = zip16(Some(0), Some(1), Some(2), Some(3), Some(4), Some(5), Some(6), Some(7), Some(8), Some(9), Some(10), Some(11), Some(12), Some(13), Some(14), Some(15));
+        assert_eq!(z.map(|t| t.15), Some(15));
+    }
+
+    #[test]
+    fn test_zip_lazy_skips_after_none() {
+        let calls = std::cell::Cell::new(0);
+        let lookup = |found: bool| {
+            calls.set(calls.get() + 1);
+            found.then_some(calls.get())
+        };
+        assert_eq!(zip3_lazy(|| lookup(true), || lookup(false), || lookup(true)), None);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(zip2_lazy(|| lookup(true), || lookup(true)), Some((3, 4)));
+    }
+
+    #[test]
+    fn test_coalesce_and_first_some() {
+        assert_eq!(coalesce([None, Some(2), Some(3)]), Some(2));
+        assert_eq!(coalesce(Vec::<Option<u8>>::new()), None);
+        let env = |key: &'static str| move || (key == "PORT").then_some(8080);
+        let defaults: [Box<dyn FnOnce() -> Option<u16>>; 3] = [Box::new(env("HTTP_PORT")), Box::new(env("PORT")), Box::new(|| unreachable!())];
+        assert_eq!(first_some(defaults), Some(8080));
+    }
+
+    #[test]
+    fn test_or_default_with_in_compose() {
+        let port = crate::compose::compose2(or_default_with(|| 80), |s: &str| s.parse::<u16>().ok());
+        assert_eq!(port("8080"), 8080);
+        assert_eq!(port("http"), 80);
+    }
+}
@@ -0,0 +1,222 @@
+// Partial application: bind one argument of a multi-argument function directly,
+// without currying it first.
+// papply(f, a)(b) == f(a, b)
+// papply_last3(f, c)(a, b) == f(a, b, c)
+// The bound argument is cloned for every call.
+
+macro_rules! papply_first {
+    ($name:ident, $a:ident: $A:ident, $($b:ident: $B:ident),+) => {
+        pub fn $name<$A, $($B,)+ R, F>(function: F, $a: $A) -> impl Fn($($B),+) -> R
+        where
+            F: Fn($A, $($B),+) -> R,
+            $A: Clone,
+        {
+            move |$($b: $B),+| function($a.clone(), $($b),+)
+        }
+    };
+}
+
+// Splits off the last parameter, since `macro_rules` can't match it directly.
+macro_rules! papply_last {
+    ($name:ident, $($p:ident: $P:ident),+) => {
+        papply_last!(@split $name; []; $($p: $P),+);
+    };
+    (@split $name:ident; [$($init:tt)*]; $z:ident: $Z:ident) => {
+        papply_last!(@gen $name; [$($init)*]; $z: $Z);
+    };
+    (@split $name:ident; [$($init:tt)*]; $a:ident: $A:ident, $($rest:tt)+) => {
+        papply_last!(@split $name; [$($init)* $a: $A,]; $($rest)+);
+    };
+    (@gen $name:ident; [$($a:ident: $A:ident,)+]; $z:ident: $Z:ident) => {
+        pub fn $name<$($A,)+ $Z, R, F>(function: F, $z: $Z) -> impl Fn($($A),+) -> R
+        where
+            F: Fn($($A,)+ $Z) -> R,
+            $Z: Clone,
+        {
+            move |$($a: $A),+| function($($a,)+ $z.clone())
+        }
+    };
+}
+
+arities!(papply_first; _, papply, papply3, papply4, papply5, papply6);
+arities!(papply_last; _, papply_last, papply_last3, papply_last4, papply_last5, papply_last6);
+
+// ---------------------------------------------------
+// Throwing versions: the same binding for functions returning `Result`, spelled out
+// so the error type is part of the signature. The error type is `X`, as `E` is taken
+// by the fifth argument.
+// ---------------------------------------------------
+
+macro_rules! papply_first_throwing {
+    ($name:ident, $a:ident: $A:ident, $($b:ident: $B:ident),+) => {
+        pub fn $name<$A, $($B,)+ R, X, F>(function: F, $a: $A) -> impl Fn($($B),+) -> Result<R, X>
+        where
+            F: Fn($A, $($B),+) -> Result<R, X>,
+            $A: Clone,
+        {
+            move |$($b: $B),+| function($a.clone(), $($b),+)
+        }
+    };
+}
+
+macro_rules! papply_last_throwing {
+    ($name:ident, $($p:ident: $P:ident),+) => {
+        papply_last_throwing!(@split $name; []; $($p: $P),+);
+    };
+    (@split $name:ident; [$($init:tt)*]; $z:ident: $Z:ident) => {
+        papply_last_throwing!(@gen $name; [$($init)*]; $z: $Z);
+    };
+    (@split $name:ident; [$($init:tt)*]; $a:ident: $A:ident, $($rest:tt)+) => {
+        papply_last_throwing!(@split $name; [$($init)* $a: $A,]; $($rest)+);
+    };
+    (@gen $name:ident; [$($a:ident: $A:ident,)+]; $z:ident: $Z:ident) => {
+        pub fn $name<$($A,)+ $Z, R, X, F>(function: F, $z: $Z) -> impl Fn($($A),+) -> Result<R, X>
+        where
+            F: Fn($($A,)+ $Z) -> Result<R, X>,
+            $Z: Clone,
+        {
+            move |$($a: $A),+| function($($a,)+ $z.clone())
+        }
+    };
+}
+
+arities!(papply_first_throwing; _, papply_throwing, papply3_throwing, papply4_throwing, papply5_throwing, papply6_throwing);
+arities!(
+    papply_last_throwing;
+    _,
+    papply_last_throwing,
+    papply_last3_throwing,
+    papply_last4_throwing,
+    papply_last5_throwing,
+    papply_last6_throwing
+);
+
+// ---------------------------------------------------
+// `partial!`: bind any arguments, leaving `_` placeholders open.
+// partial!(validate_range(0.0, _, limits)) == move |x| validate_range(0.0, x, limits)
+// ---------------------------------------------------
+
+/// Partially apply a function call, turning each `_` argument into a parameter of the
+/// returned closure, in order. The other arguments are evaluated once, when the closure
+/// is created, and cloned for every call, so the closure is `Fn` and each bound
+/// argument must be `Clone`. The callee is a plain path such as `check` or `Range::new`.
+#[macro_export]
+macro_rules! partial {
+    ($($f:ident)::+ ( $($args:tt)* )) => {
+        $crate::partial!(@munch [$($f)::+]; []; []; []; $($args)*)
+    };
+    // @munch [callee]; [let bindings]; [closure params]; [call arguments]; unparsed arguments
+    (@munch [$($f:tt)*]; [$($bind:tt)*]; [$($param:ident)*]; [$($call:tt)*]; ) => {{
+        $($bind)*
+        move |$($param),*| $($f)*($($call)*)
+    }};
+    (@munch [$($f:tt)*]; [$($bind:tt)*]; [$($param:ident)*]; [$($call:tt)*]; _ $(, $($rest:tt)*)?) => {
+        $crate::partial!(@munch [$($f)*]; [$($bind)*]; [$($param)* arg]; [$($call)* arg,]; $($($rest)*)?)
+    };
+    (@munch [$($f:tt)*]; [$($bind:tt)*]; [$($param:ident)*]; [$($call:tt)*]; $e:expr $(, $($rest:tt)*)?) => {
+        $crate::partial!(
+            @munch [$($f)*];
+            [$($bind)* let bound = $e;];
+            [$($param)*];
+            [$($call)* ::core::clone::Clone::clone(&bound),];
+            $($($rest)*)?
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compose::compose2_res;
+
+    #[test]
+    fn test_papply_and_papply_last() {
+        let sub = |a: i32, b: i32| a - b;
+        assert_eq!(papply(sub, 10)(3), 7);
+        assert_eq!(papply_last(sub, 10)(3), -7);
+    }
+
+    #[test]
+    fn test_papply_clones_bound_argument() {
+        let greet = |greeting: String, name: &str| format!("{}, {}!", greeting, name);
+        let hello = papply(greet, "Hello".to_string());
+        let greetings: Vec<String> = ["Ann", "Bob"].into_iter().map(hello).collect();
+        assert_eq!(greetings, vec!["Hello, Ann!", "Hello, Bob!"]);
+    }
+
+    #[test]
+    fn test_papply6_and_papply_last6() {
+        let f = |a: i32, b: i32, c: i32, d: i32, e: i32, g: i32| a * 100_000 + b * 10_000 + c * 1_000 + d * 100 + e * 10 + g;
+        assert_eq!(papply6(f, 1)(2, 3, 4, 5, 6), 123_456);
+        assert_eq!(papply_last6(f, 6)(1, 2, 3, 4, 5), 123_456);
+        assert_eq!(papply_last3(|a: i32, b: i32, c: i32| a + b * c, 10)(1, 2), 21);
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum FormError {
+        Empty,
+        BadLength(usize),
+    }
+
+    fn check_length(min: usize, input: &str, max: usize) -> Result<&str, FormError> {
+        match input.len() {
+            0 => Err(FormError::Empty),
+            n if n < min || n > max => Err(FormError::BadLength(n)),
+            _ => Ok(input),
+        }
+    }
+
+    #[test]
+    fn test_papply_throwing_in_a_pipeline() {
+        let at_most_5 = papply_last3_throwing(check_length, 5);
+        let username = papply_throwing(at_most_5, 1);
+        let shout = compose2_res(|s: &str| Ok::<_, FormError>(s.to_uppercase()), username);
+        assert_eq!(shout("ann"), Ok("ANN".to_string()));
+        assert_eq!(shout(""), Err(FormError::Empty));
+        assert_eq!(shout("annabel"), Err(FormError::BadLength(7)));
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Limits {
+        max: f64,
+    }
+
+    fn validate_range(min: f64, value: f64, limits: Limits) -> Result<f64, String> {
+        if value < min || value > limits.max {
+            Err(format!("{} outside {}..={}", value, min, limits.max))
+        } else {
+            Ok(value)
+        }
+    }
+
+    #[test]
+    fn test_partial_macro_placeholder() {
+        let limits = Limits { max: 100.0 };
+        let percentage = partial!(validate_range(0.0, _, limits));
+        assert_eq!(percentage(42.0), Ok(42.0));
+        assert_eq!(percentage(120.0), Err("120 outside 0..=100".to_string()));
+    }
+
+    #[test]
+    fn test_partial_macro_several_placeholders_and_paths() {
+        let clamp = partial!(f64::clamp(_, 0.0, _));
+        assert_eq!(clamp(5.0, 1.0), 1.0);
+        let between = partial!(validate_range(_, _, Limits { max: 10.0 }));
+        assert_eq!(between(1.0, 5.0), Ok(5.0));
+        let fixed = partial!(validate_range(1.0, 2.0, Limits { max: 3.0 },));
+        assert_eq!(fixed(), Ok(2.0));
+    }
+
+    #[test]
+    fn test_partial_macro_evaluates_bound_arguments_once() {
+        let calls = std::cell::Cell::new(0);
+        let limit = || {
+            calls.set(calls.get() + 1);
+            Limits { max: 1.0 }
+        };
+        let unit = partial!(validate_range(0.0, _, limit()));
+        let checked: Vec<_> = [0.5, 2.0].into_iter().map(unit).collect();
+        assert_eq!(checked, vec![Ok(0.5), Err("2 outside 0..=1".to_string())]);
+        assert_eq!(calls.get(), 1);
+    }
+}
@@ -0,0 +1,328 @@
+use core::fmt::{self, Debug};
+use core::str::FromStr;
+
+use crate::prelude::*;
+use crate::keypath::KeyPath;
+
+/// One recorded field change, in a form that can be logged or serialized, and turned
+/// back into a change with [`Patch::from_entries`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchEntry {
+    pub path: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// A change of one field, with the keypath's type hidden so a `Patch` can hold
+/// changes of differently typed fields.
+trait Change<Root> {
+    fn apply(&self, root: &mut Root);
+    fn inverted(&self) -> Box<dyn Change<Root>>;
+    fn entry(&self) -> PatchEntry;
+    fn boxed_clone(&self) -> Box<dyn Change<Root>>;
+}
+
+struct FieldChange<Root, Value> {
+    path: &'static str,
+    keypath: KeyPath<Root, Value>,
+    old: Value,
+    new: Value,
+}
+
+impl<Root: 'static, Value> Change<Root> for FieldChange<Root, Value>
+where
+    Value: Clone + Debug + 'static,
+{
+    fn apply(&self, root: &mut Root) {
+        (self.keypath.set)(root, self.new.clone())
+    }
+
+    fn inverted(&self) -> Box<dyn Change<Root>> {
+        Box::new(FieldChange { path: self.path, keypath: self.keypath, old: self.new.clone(), new: self.old.clone() })
+    }
+
+    fn entry(&self) -> PatchEntry {
+        PatchEntry { path: self.path.to_string(), old: format!("{:?}", self.old), new: format!("{:?}", self.new) }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Change<Root>> {
+        Box::new(FieldChange { path: self.path, keypath: self.keypath, old: self.old.clone(), new: self.new.clone() })
+    }
+}
+
+/// Why entries could not be turned back into a `Patch`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    UnknownPath(String),
+    /// `value` (an entry's `old` or `new`) does not parse as the field's type.
+    InvalidValue { path: String, value: String },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::UnknownPath(path) => write!(f, "unknown path `{path}`"),
+            PatchError::InvalidValue { path, value } => write!(f, "invalid value {value} for `{path}`"),
+        }
+    }
+}
+
+impl core::error::Error for PatchError {}
+
+type Decoder<Root> = Box<dyn Fn(&PatchEntry) -> Result<Box<dyn Change<Root>>, PatchError>>;
+
+/// The fields a patch may touch, by path, so entries can be parsed back into changes.
+/// Values are read with `FromStr`, after undoing the quoting and escaping that `Debug`
+/// adds to strings and chars; any type whose `Debug` form parses with `FromStr`
+/// (numbers, `bool`, `String`, `char`, ...) round-trips.
+pub struct PatchFields<Root> {
+    decoders: Vec<(&'static str, Decoder<Root>)>,
+}
+
+impl<Root: 'static> PatchFields<Root> {
+    pub fn new() -> Self {
+        Self { decoders: Vec::new() }
+    }
+
+    pub fn field<Value>(mut self, path: &'static str, keypath: KeyPath<Root, Value>) -> Self
+    where
+        Value: Clone + Debug + FromStr + 'static,
+    {
+        let parse = move |value: &str| {
+            unquote_debug(value)
+                .and_then(|s| s.parse::<Value>().ok())
+                .ok_or_else(|| PatchError::InvalidValue { path: path.to_string(), value: value.to_string() })
+        };
+        self.decoders.push((
+            path,
+            Box::new(move |entry: &PatchEntry| {
+                let (old, new) = (parse(&entry.old)?, parse(&entry.new)?);
+                Ok(Box::new(FieldChange { path, keypath, old, new }) as Box<dyn Change<Root>>)
+            }),
+        ));
+        self
+    }
+}
+
+impl<Root: 'static> Default for PatchFields<Root> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The text of a `Debug`-formatted string or char literal, or `s` itself if it is not
+/// quoted. `None` for an unknown escape.
+fn unquote_debug(s: &str) -> Option<String> {
+    let inner = match s.as_bytes() {
+        [b'"', .., b'"'] | [b'\'', .., b'\''] => &s[1..s.len() - 1],
+        _ => return Some(s.to_string()),
+    };
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            'u' => {
+                let hex: String = chars.by_ref().skip_while(|&c| c == '{').take_while(|&c| c != '}').collect();
+                char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+            }
+            other => other,
+        });
+    }
+    Some(out)
+}
+
+/// Keypath-level changes to a `Root`: recorded from `set` calls or by diffing two
+/// instances, then applied, inverted, or turned into `PatchEntry`s for audit logs and sync.
+pub struct Patch<Root> {
+    changes: Vec<Box<dyn Change<Root>>>,
+}
+
+impl<Root: 'static> Patch<Root> {
+    pub fn new() -> Self {
+        Self { changes: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Set a field on `root` and record the change under `path`.
+    pub fn set<Value>(&mut self, root: &mut Root, path: &'static str, keypath: KeyPath<Root, Value>, value: Value)
+    where
+        Value: Clone + Debug + 'static,
+    {
+        let old = (keypath.get)(root).clone();
+        (keypath.set)(root, value.clone());
+        self.changes.push(Box::new(FieldChange { path, keypath, old, new: value }));
+    }
+
+    /// Record the field's change from `before` to `after`, if it changed.
+    pub fn diff_field<Value>(mut self, path: &'static str, keypath: KeyPath<Root, Value>, before: &Root, after: &Root) -> Self
+    where
+        Value: Clone + Debug + PartialEq + 'static,
+    {
+        let (old, new) = ((keypath.get)(before), (keypath.get)(after));
+        if old != new {
+            self.changes.push(Box::new(FieldChange { path, keypath, old: old.clone(), new: new.clone() }));
+        }
+        self
+    }
+
+    /// Set every recorded field to its new value, in recording order.
+    pub fn apply(&self, root: &mut Root) {
+        self.changes.iter().for_each(|change| change.apply(root))
+    }
+
+    /// Owned version of [`Patch::apply`], for use in pipelines.
+    pub fn apply_to(&self, mut root: Root) -> Root {
+        self.apply(&mut root);
+        root
+    }
+
+    /// The patch undoing this one: old and new values swapped, in reverse order.
+    pub fn invert(&self) -> Patch<Root> {
+        Patch { changes: self.changes.iter().rev().map(|change| change.inverted()).collect() }
+    }
+
+    /// The recorded changes, oldest first, with values in their `Debug` form.
+    pub fn entries(&self) -> Vec<PatchEntry> {
+        self.changes.iter().map(|change| change.entry()).collect()
+    }
+
+    /// Rebuild a patch from its entries, e.g. after receiving them from another process.
+    pub fn from_entries(entries: impl IntoIterator<Item = PatchEntry>, fields: &PatchFields<Root>) -> Result<Self, PatchError> {
+        let changes = entries
+            .into_iter()
+            .map(|entry| {
+                let (_, decode) = fields
+                    .decoders
+                    .iter()
+                    .find(|(path, _)| *path == entry.path)
+                    .ok_or_else(|| PatchError::UnknownPath(entry.path.clone()))?;
+                decode(&entry)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Patch { changes })
+    }
+}
+
+impl<Root: 'static> Clone for Patch<Root> {
+    fn clone(&self) -> Self {
+        Patch { changes: self.changes.iter().map(|change| change.boxed_clone()).collect() }
+    }
+}
+
+impl<Root: 'static> Default for Patch<Root> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Device {
+        name: String,
+        brightness: u8,
+        on: bool,
+    }
+
+    fn name() -> KeyPath<Device, String> {
+        KeyPath::new(|d: &Device| &d.name, |d, v| d.name = v)
+    }
+
+    fn brightness() -> KeyPath<Device, u8> {
+        KeyPath::new(|d: &Device| &d.brightness, |d, v| d.brightness = v)
+    }
+
+    fn on() -> KeyPath<Device, bool> {
+        KeyPath::new(|d: &Device| &d.on, |d, v| d.on = v)
+    }
+
+    fn lamp() -> Device {
+        Device { name: "lamp".into(), brightness: 10, on: false }
+    }
+
+    #[test]
+    fn test_diff_and_apply() {
+        let before = lamp();
+        let after = Device { brightness: 80, on: true, ..lamp() };
+        let patch = Patch::new()
+            .diff_field("name", name(), &before, &after)
+            .diff_field("brightness", brightness(), &before, &after)
+            .diff_field("on", on(), &before, &after);
+        assert_eq!(patch.len(), 2, "unchanged fields are not recorded");
+        assert_eq!(patch.apply_to(before.clone()), after);
+        assert_eq!(patch.invert().apply_to(after), before);
+    }
+
+    #[test]
+    fn test_record_set_calls_and_entries() {
+        let mut device = lamp();
+        let mut patch = Patch::new();
+        patch.set(&mut device, "on", on(), true);
+        patch.set(&mut device, "name", name(), "desk lamp".into());
+        assert_eq!(device, Device { name: "desk lamp".into(), brightness: 10, on: true });
+        assert_eq!(
+            patch.entries(),
+            vec![
+                PatchEntry { path: "on".into(), old: "false".into(), new: "true".into() },
+                PatchEntry { path: "name".into(), old: "\"lamp\"".into(), new: "\"desk lamp\"".into() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invert_reverses_order_for_repeated_fields() {
+        let mut device = lamp();
+        let mut patch = Patch::new();
+        patch.set(&mut device, "brightness", brightness(), 20);
+        patch.set(&mut device, "brightness", brightness(), 30);
+        let undo = patch.clone().invert();
+        undo.apply(&mut device);
+        assert_eq!(device, lamp());
+        assert!(Patch::<Device>::default().is_empty());
+    }
+
+    fn fields() -> PatchFields<Device> {
+        PatchFields::new().field("name", name()).field("brightness", brightness()).field("on", on())
+    }
+
+    #[test]
+    fn test_entries_round_trip() {
+        let mut device = lamp();
+        let mut patch = Patch::new();
+        patch.set(&mut device, "name", name(), "say \"hi\"\n\u{1b}".into());
+        patch.set(&mut device, "brightness", brightness(), 55);
+        patch.set(&mut device, "on", on(), true);
+        let received = Patch::from_entries(patch.entries(), &fields()).unwrap();
+        assert_eq!(received.entries(), patch.entries());
+        assert_eq!(received.apply_to(lamp()), device);
+        assert_eq!(received.invert().apply_to(device), lamp());
+    }
+
+    #[test]
+    fn test_from_entries_errors() {
+        let entry = |path: &str, new: &str| PatchEntry { path: path.into(), old: "1".into(), new: new.into() };
+        assert_eq!(
+            Patch::from_entries([entry("color", "2")], &fields()).err(),
+            Some(PatchError::UnknownPath("color".into()))
+        );
+        let err = Patch::from_entries([entry("brightness", "300")], &fields()).err().unwrap();
+        assert_eq!(err.to_string(), "invalid value 300 for `brightness`");
+    }
+}
@@ -0,0 +1,184 @@
+// Method-style piping: `value.pipe(f).pipe(g)` == g(f(value))
+// Reads left-to-right like Swift's `|>`, without wrapping the whole chain upfront.
+
+/// Blanket extension trait giving every value `pipe`, `pipe_ref` and `try_pipe`.
+pub trait Pipe {
+    /// Feed `self` into `f` by value.
+    fn pipe<B>(self, f: impl FnOnce(Self) -> B) -> B
+    where
+        Self: Sized,
+    {
+        f(self)
+    }
+
+    /// Feed a borrow of `self` into `f`, leaving `self` usable afterwards.
+    fn pipe_ref<'a, B>(&'a self, f: impl FnOnce(&'a Self) -> B) -> B {
+        f(self)
+    }
+
+    /// Feed `self` into a fallible step, so the chain can continue with `?` or `and_then`.
+    fn try_pipe<B, E>(self, f: impl FnOnce(Self) -> Result<B, E>) -> Result<B, E>
+    where
+        Self: Sized,
+    {
+        f(self)
+    }
+}
+
+impl<T: ?Sized> Pipe for T {}
+
+// Free-function forward composition for stages that are not `Fn`.
+
+/// `g(f(x))` for `FnMut` stages, e.g. ones that count or accumulate. The result is
+/// only `FnMut`, so it has to be bound `mut` to be called.
+pub fn pipe_mut<A, B, C, F, G>(mut f: F, mut g: G) -> impl FnMut(A) -> C
+where
+    F: FnMut(A) -> B,
+    G: FnMut(B) -> C,
+{
+    move |a: A| g(f(a))
+}
+
+/// `g(f(x))` for `FnOnce` stages, e.g. ones that consume what they capture. The
+/// result is only `FnOnce` and can be called a single time.
+pub fn pipe_once<A, B, C, F, G>(f: F, g: G) -> impl FnOnce(A) -> C
+where
+    F: FnOnce(A) -> B,
+    G: FnOnce(B) -> C,
+{
+    move |a: A| g(f(a))
+}
+
+// pipe7..pipe10: forward composition of long chains without nesting,
+// pipe7(a, b, c, d, e, g, h)(x) == h(g(e(d(c(b(a(x))))))).
+// Each stage's output type is the uppercase of its name, and `T` is the input.
+macro_rules! pipe_forward {
+    ($name:ident, $($p:tt)+) => {
+        pipe_forward!(@munch $name; T; []; $($p)+);
+    };
+    (@munch $name:ident; $Z:ident; [$($f:ident: $In:ident -> $Out:ident,)+];) => {
+        #[allow(clippy::too_many_arguments)]
+        pub fn $name<T, $($Out),+>($($f: impl Fn($In) -> $Out),+) -> impl Fn(T) -> $Z {
+            move |x: T| {
+                $(let x = $f(x);)+
+                x
+            }
+        }
+    };
+    (@munch $name:ident; $In:ident; [$($done:tt)*]; $f:ident: $Out:ident $(, $($rest:tt)+)?) => {
+        pipe_forward!(@munch $name; $Out; [$($done)* $f: $In -> $Out,]; $($($rest)+)?);
+    };
+}
+
+// The throwing versions stop at the first `Err`. Every stage shares the error type `X`,
+// as `E` is taken by the fifth stage.
+macro_rules! pipe_throwing {
+    ($name:ident, $($p:tt)+) => {
+        pipe_throwing!(@munch $name; T; []; $($p)+);
+    };
+    (@munch $name:ident; $Z:ident; [$($f:ident: $In:ident -> $Out:ident,)+];) => {
+        #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+        pub fn $name<T, $($Out,)+ X>($($f: impl Fn($In) -> Result<$Out, X>),+) -> impl Fn(T) -> Result<$Z, X> {
+            move |x: T| {
+                $(let x = $f(x)?;)+
+                Ok(x)
+            }
+        }
+    };
+    (@munch $name:ident; $In:ident; [$($done:tt)*]; $f:ident: $Out:ident $(, $($rest:tt)+)?) => {
+        pipe_throwing!(@munch $name; $Out; [$($done)* $f: $In -> $Out,]; $($($rest)+)?);
+    };
+}
+
+arities!(pipe_forward; _, _, _, _, _, _, pipe7, pipe8, pipe9, pipe10);
+arities!(pipe_throwing; _, _, _, _, _, _, pipe7_throwing, pipe8_throwing, pipe9_throwing, pipe10_throwing);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipe() {
+        let result = 3.pipe(|x| x + 1).pipe(|x| x * 2).pipe(|x| x.to_string());
+        assert_eq!(result, "8");
+    }
+
+    #[test]
+    fn test_pipe_with_fn_items() {
+        assert_eq!("  hi  ".pipe(str::trim).pipe(str::len), 2);
+    }
+
+    #[test]
+    fn test_pipe_ref_keeps_value() {
+        let items = vec![1, 2, 3];
+        let total: i32 = items.pipe_ref(|v| v.iter().sum());
+        assert_eq!(total, 6);
+        assert_eq!(items.len(), 3);
+        assert_eq!("abc".pipe_ref(|s| s.len()), 3);
+    }
+
+    #[test]
+    fn test_try_pipe_with_question_mark() {
+        fn run(input: &str) -> Result<i32, std::num::ParseIntError> {
+            let doubled = input.try_pipe(str::parse::<i32>)?.pipe(|n| n * 2);
+            Ok(doubled)
+        }
+        assert_eq!(run("21"), Ok(42));
+        assert!(run("x").is_err());
+    }
+
+    #[test]
+    fn test_try_pipe_and_then() {
+        let checked = |n: i32| if n > 0 { Ok(n) } else { Err("not positive".to_string()) };
+        let result = "5"
+            .try_pipe(|s| s.parse::<i32>().map_err(|e| e.to_string()))
+            .and_then(checked);
+        assert_eq!(result, Ok(5));
+    }
+
+    #[test]
+    fn test_pipe_mut_numbers_lines() {
+        let mut next = 0;
+        let mut number = pipe_mut(str::trim, |line: &str| {
+            next += 1;
+            format!("{}: {}", next, line)
+        });
+        assert_eq!(number(" a "), "1: a");
+        assert_eq!(number("b"), "2: b");
+    }
+
+    #[test]
+    fn test_pipe_once_consumes_captures() {
+        let header = String::from("id,name");
+        let prepend = move |rows: Vec<String>| {
+            let mut out = vec![header];
+            out.extend(rows);
+            out
+        };
+        let render = pipe_once(prepend, |lines: Vec<String>| lines.join("\n"));
+        assert_eq!(render(vec!["1,ada".to_string()]), "id,name\n1,ada");
+    }
+
+    #[test]
+    fn test_pipe7_and_pipe10() {
+        let inc = |n: i32| n + 1;
+        let f = pipe7(|s: &str| s.len() as i32, inc, inc, inc, inc, |n| n * 2, |n| n.to_string());
+        assert_eq!(f("abc"), "14");
+        let g = pipe10(inc, inc, inc, inc, inc, inc, inc, inc, inc, |n: i32| format!("{}!", n));
+        assert_eq!(g(0), "9!");
+    }
+
+    #[test]
+    fn test_pipe10_throwing_stops_at_first_error() {
+        let step = |limit: i32| move |n: i32| if n < limit { Ok(n + 1) } else { Err(format!("{} reached {}", n, limit)) };
+        let f = pipe10_throwing(
+            |s: &str| s.parse::<i32>().map_err(|e| e.to_string()),
+            step(100), step(100), step(100), step(100), step(100), step(100), step(100), step(100),
+            step(5),
+        );
+        assert_eq!(f("-10"), Ok(-1));
+        assert_eq!(f("0"), Err("8 reached 5".to_string()));
+        assert!(f("zero").is_err());
+        assert_eq!(pipe7_throwing(step(9), step(9), step(9), step(9), step(9), step(9), step(9))(0), Ok(7));
+    }
+}
@@ -0,0 +1,232 @@
+use core::marker::PhantomData;
+
+use crate::prelude::*;
+use crate::keypath::KeyPath;
+
+/// A condition on `T`: a wrapped `Fn(&T) -> bool` with boolean combinators.
+/// As with `Func`, the default `F` is boxed so `Predicate<T>` can be stored in rule tables.
+pub struct Predicate<T: ?Sized, F = Box<dyn Fn(&T) -> bool>> {
+    f: F,
+    _marker: PhantomData<fn(&T) -> bool>,
+}
+
+impl<T: ?Sized, F> Predicate<T, F>
+where
+    F: Fn(&T) -> bool,
+{
+    pub fn new(f: F) -> Self {
+        Self { f, _marker: PhantomData }
+    }
+
+    pub fn test(&self, t: &T) -> bool {
+        (self.f)(t)
+    }
+
+    /// Both must hold; `other` is not evaluated if `self` fails.
+    pub fn and<G>(self, other: Predicate<T, G>) -> Predicate<T, impl Fn(&T) -> bool>
+    where
+        G: Fn(&T) -> bool,
+    {
+        let (f, g) = (self.f, other.f);
+        Predicate::new(move |t: &T| f(t) && g(t))
+    }
+
+    /// Either must hold; `other` is not evaluated if `self` holds.
+    pub fn or<G>(self, other: Predicate<T, G>) -> Predicate<T, impl Fn(&T) -> bool>
+    where
+        G: Fn(&T) -> bool,
+    {
+        let (f, g) = (self.f, other.f);
+        Predicate::new(move |t: &T| f(t) || g(t))
+    }
+
+    // Named like the boolean operator rather than implementing `core::ops::Not`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Predicate<T, impl Fn(&T) -> bool> {
+        let f = self.f;
+        Predicate::new(move |t: &T| !f(t))
+    }
+
+    /// Back to a plain closure, e.g. for `suites::filter` or `Iterator::filter`.
+    pub fn into_fn(self) -> F {
+        self.f
+    }
+
+    pub fn boxed(self) -> Predicate<T>
+    where
+        F: 'static,
+    {
+        Predicate::new(Box::new(self.f) as Box<dyn Fn(&T) -> bool>)
+    }
+}
+
+/// A predicate on a root that tests the value a keypath points at.
+pub fn keypath_satisfies<Root, Value, P>(keypath: KeyPath<Root, Value>, p: Predicate<Value, P>) -> Predicate<Root, impl Fn(&Root) -> bool>
+where
+    P: Fn(&Value) -> bool,
+{
+    let p = p.into_fn();
+    Predicate::new(move |root: &Root| p((keypath.get)(root)))
+}
+
+/// Holds when every predicate holds (vacuously true for none); stops at the first failure.
+pub fn all_pass<T: ?Sized, F>(predicates: impl IntoIterator<Item = Predicate<T, F>>) -> Predicate<T, impl Fn(&T) -> bool>
+where
+    F: Fn(&T) -> bool,
+{
+    let predicates: Vec<F> = predicates.into_iter().map(Predicate::into_fn).collect();
+    Predicate::new(move |t: &T| predicates.iter().all(|p| p(t)))
+}
+
+/// Holds when some predicate holds (false for none); stops at the first success.
+pub fn any_pass<T: ?Sized, F>(predicates: impl IntoIterator<Item = Predicate<T, F>>) -> Predicate<T, impl Fn(&T) -> bool>
+where
+    F: Fn(&T) -> bool,
+{
+    let predicates: Vec<F> = predicates.into_iter().map(Predicate::into_fn).collect();
+    Predicate::new(move |t: &T| predicates.iter().any(|p| p(t)))
+}
+
+// --- Complement: negate a boolean function, keeping its argument list as is, by value
+// or by reference. `complement_ref` is the form `filter` and `Iterator::filter` need,
+// since their predicates must accept a borrow of any lifetime.
+
+/// `!f(a)`.
+pub fn complement<A, F>(f: F) -> impl Fn(A) -> bool
+where
+    F: Fn(A) -> bool,
+{
+    move |a: A| !f(a)
+}
+
+/// `!f(&a)`, for predicates over references, e.g. in `filter`.
+pub fn complement_ref<A: ?Sized, F>(f: F) -> impl Fn(&A) -> bool
+where
+    F: Fn(&A) -> bool,
+{
+    move |a: &A| !f(a)
+}
+
+pub fn complement2<A, B, F>(f: F) -> impl Fn(A, B) -> bool
+where
+    F: Fn(A, B) -> bool,
+{
+    move |a: A, b: B| !f(a, b)
+}
+
+pub fn complement3<A, B, C, F>(f: F) -> impl Fn(A, B, C) -> bool
+where
+    F: Fn(A, B, C) -> bool,
+{
+    move |a: A, b: B, c: C| !f(a, b, c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::suites::filter;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Sensor {
+        room: &'static str,
+        temperature: f64,
+    }
+
+    fn temperature() -> KeyPath<Sensor, f64> {
+        KeyPath::new(|s: &Sensor| &s.temperature, |s, t| s.temperature = t)
+    }
+
+    #[test]
+    fn test_and_or_not() {
+        let positive = || Predicate::new(|n: &i32| *n > 0);
+        let even = || Predicate::new(|n: &i32| n % 2 == 0);
+        assert!(positive().and(even()).test(&4));
+        assert!(!positive().and(even()).test(&3));
+        assert!(positive().or(even()).test(&-2));
+        assert!(positive().not().test(&-1));
+    }
+
+    #[test]
+    fn test_keypath_satisfies_with_filter() {
+        let hot = keypath_satisfies(temperature(), Predicate::new(|t: &f64| *t > 25.0));
+        let in_kitchen = Predicate::new(|s: &Sensor| s.room == "kitchen");
+        let rule = hot.and(in_kitchen.not());
+        let sensors = vec![
+            Sensor { room: "kitchen", temperature: 30.0 },
+            Sensor { room: "bedroom", temperature: 28.0 },
+            Sensor { room: "bedroom", temperature: 20.0 },
+        ];
+        assert_eq!(filter(sensors, rule.into_fn()), vec![Sensor { room: "bedroom", temperature: 28.0 }]);
+    }
+
+    #[test]
+    fn test_boxed_predicates_in_a_table() {
+        let rules: Vec<(&str, Predicate<str>)> = vec![
+            ("empty", Predicate::new(|s: &str| s.is_empty()).boxed()),
+            ("shouting", Predicate::new(|s: &str| s.chars().all(char::is_uppercase)).boxed()),
+        ];
+        let matched: Vec<&str> = rules.iter().filter(|(_, p)| p.test("HEY")).map(|(name, _)| *name).collect();
+        assert_eq!(matched, vec!["shouting"]);
+    }
+
+    #[test]
+    fn test_all_pass_and_any_pass() {
+        let checks = || -> Vec<Predicate<str>> {
+            vec![
+                Predicate::new(|s: &str| !s.is_empty()).boxed(),
+                Predicate::new(|s: &str| s.len() <= 8).boxed(),
+                Predicate::new(|s: &str| s.chars().all(char::is_alphanumeric)).boxed(),
+            ]
+        };
+        let valid_username = all_pass(checks());
+        assert!(valid_username.test("alice42"));
+        assert!(!valid_username.test("alice_42"));
+        assert!(any_pass(checks()).test("alice_42"));
+    }
+
+    #[test]
+    fn test_all_pass_short_circuits() {
+        let calls = std::cell::Cell::new(0);
+        let counted = |result: bool| {
+            let calls = &calls;
+            Predicate::new(move |_: &()| {
+                calls.set(calls.get() + 1);
+                result
+            })
+        };
+        assert!(!all_pass([counted(false), counted(true)]).test(&()));
+        assert!(any_pass([counted(true), counted(false)]).test(&()));
+        assert_eq!(calls.get(), 2);
+        assert!(all_pass(Vec::<Predicate<()>>::new()).test(&()));
+        assert!(!any_pass(Vec::<Predicate<()>>::new()).test(&()));
+    }
+
+    #[test]
+    fn test_complement_in_filter() {
+        fn is_blank(s: &&str) -> bool {
+            s.trim().is_empty()
+        }
+        assert_eq!(filter(["a", " ", "b"], complement_ref(is_blank)), vec!["a", "b"]);
+        assert!(complement_ref(str::is_empty)("x"));
+    }
+
+    #[test]
+    fn test_complement2_and_3() {
+        let differ = complement2(|a: &i32, b: &i32| a == b);
+        assert!(differ(&1, &2));
+        let outside = complement3(|x: &i32, lo: &i32, hi: &i32| (lo..=hi).contains(&x));
+        assert!(outside(&11, &0, &10));
+        assert!(!outside(&5, &0, &10));
+    }
+
+    #[test]
+    fn test_complement_by_value() {
+        let non_positive = complement(|n: i32| n > 0);
+        assert!(non_positive(0));
+        assert!(!non_positive(3));
+        let owned = complement(|s: String| s.is_empty());
+        assert!(owned("x".to_string()));
+        let unequal = complement2(|a: i32, b: i32| a == b);
+        assert!(unequal(1, 2));
+    }
+}
@@ -0,0 +1,141 @@
+use core::marker::PhantomData;
+
+use crate::prelude::*;
+
+/// A computation that needs read-only access to an environment `Env` (configuration,
+/// thresholds, lookup tables) to produce an `A`: a wrapped `Fn(&Env) -> A`.
+/// As with `Func`, the default `F` is boxed so `Reader<Env, A>` can be stored.
+pub struct Reader<Env, A, F = Box<dyn Fn(&Env) -> A>> {
+    f: F,
+    _marker: PhantomData<fn(&Env) -> A>,
+}
+
+impl<Env, A, F> Reader<Env, A, F>
+where
+    F: Fn(&Env) -> A,
+{
+    /// Adapt a plain closure over the environment.
+    pub fn new(f: F) -> Self {
+        Self { f, _marker: PhantomData }
+    }
+
+    /// Supply the environment.
+    pub fn run(&self, env: &Env) -> A {
+        (self.f)(env)
+    }
+
+    /// Back to a plain closure.
+    pub fn into_fn(self) -> F {
+        self.f
+    }
+
+    pub fn map<B, G>(self, g: G) -> Reader<Env, B, impl Fn(&Env) -> B>
+    where
+        G: Fn(A) -> B,
+    {
+        let f = self.f;
+        Reader::new(move |env: &Env| g(f(env)))
+    }
+
+    /// Continue with a reader chosen from this one's result; both see the same environment.
+    pub fn and_then<B, G, R>(self, g: G) -> Reader<Env, B, impl Fn(&Env) -> B>
+    where
+        G: Fn(A) -> Reader<Env, B, R>,
+        R: Fn(&Env) -> B,
+    {
+        let f = self.f;
+        Reader::new(move |env: &Env| g(f(env)).run(env))
+    }
+
+    pub fn zip<B, G>(self, other: Reader<Env, B, G>) -> Reader<Env, (A, B), impl Fn(&Env) -> (A, B)>
+    where
+        G: Fn(&Env) -> B,
+    {
+        let f = self.f;
+        let g = other.f;
+        Reader::new(move |env: &Env| (f(env), g(env)))
+    }
+
+    /// Run against an environment derived from a larger one, e.g. one field of an app config.
+    pub fn local<Env2, G>(self, g: G) -> Reader<Env2, A, impl Fn(&Env2) -> A>
+    where
+        G: Fn(&Env2) -> Env,
+    {
+        let f = self.f;
+        Reader::new(move |env2: &Env2| f(&g(env2)))
+    }
+
+    pub fn boxed(self) -> Reader<Env, A>
+    where
+        F: 'static,
+    {
+        Reader::new(Box::new(self.f) as Box<dyn Fn(&Env) -> A>)
+    }
+}
+
+/// A reader returning the environment itself.
+pub fn ask<Env: Clone>() -> Reader<Env, Env, impl Fn(&Env) -> Env> {
+    Reader::new(|env: &Env| env.clone())
+}
+
+/// A reader ignoring the environment.
+pub fn pure<Env, A: Clone>(a: A) -> Reader<Env, A, impl Fn(&Env) -> A> {
+    Reader::new(move |_: &Env| a.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct Config {
+        threshold: f64,
+        currency: &'static str,
+    }
+
+    fn config() -> Config {
+        Config { threshold: 100.0, currency: "EUR" }
+    }
+
+    #[test]
+    fn test_run_and_map() {
+        let threshold = Reader::new(|c: &Config| c.threshold).map(|t| t * 2.0);
+        assert_eq!(threshold.run(&config()), 200.0);
+    }
+
+    #[test]
+    fn test_and_then_sees_same_env() {
+        let is_large = |amount: f64| Reader::new(move |c: &Config| amount > c.threshold);
+        let check = Reader::new(|c: &Config| c.threshold + 1.0).and_then(is_large);
+        assert!(check.run(&config()));
+    }
+
+    #[test]
+    fn test_zip() {
+        let both = Reader::new(|c: &Config| c.currency).zip(Reader::new(|c: &Config| c.threshold));
+        assert_eq!(both.run(&config()), ("EUR", 100.0));
+    }
+
+    #[test]
+    fn test_local() {
+        struct App {
+            payments: Config,
+        }
+        let currency = Reader::new(|c: &Config| c.currency).local(|app: &App| app.payments.clone());
+        assert_eq!(currency.run(&App { payments: config() }), "EUR");
+    }
+
+    #[test]
+    fn test_ask_pure_and_boxed() {
+        let env: Reader<i32, i32> = ask::<i32>().map(|n| n + 1).boxed();
+        assert_eq!(env.run(&41), 42);
+        assert_eq!(pure::<i32, _>("constant").run(&0), "constant");
+    }
+
+    #[test]
+    fn test_into_fn_in_pipeline() {
+        let validate = Reader::new(|c: &Config| c.threshold).into_fn();
+        let amounts: Vec<f64> = [config(), Config { threshold: 5.0, currency: "USD" }].iter().map(validate).collect();
+        assert_eq!(amounts, vec![100.0, 5.0]);
+    }
+}
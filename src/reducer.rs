@@ -0,0 +1,379 @@
+// Reducers: `(state, action)` functions that update state in place and may return an
+// `Effect` of follow-up actions. Small reducers over parts of the state are lifted
+// with `pullback` and merged with `combine`; a `Store` runs them behind middleware.
+
+use alloc::collections::VecDeque;
+
+use crate::prelude::*;
+use crate::case_path::CasePath;
+use crate::keypath::KeyPath;
+
+#[cfg(feature = "futures")]
+use futures::future::{FutureExt, LocalBoxFuture};
+
+// --- Effects
+
+/// Follow-up actions returned by a reducer: a batch sent synchronously right after the
+/// current action, and, with the `futures` feature, futures whose outputs are sent
+/// once they complete (see `Store::settle`).
+pub struct Effect<Action> {
+    actions: Vec<Action>,
+    #[cfg(feature = "futures")]
+    futures: Vec<LocalBoxFuture<'static, Action>>,
+}
+
+impl<Action: 'static> Effect<Action> {
+    pub fn none() -> Self {
+        Self::batch(Vec::new())
+    }
+
+    pub fn send(action: Action) -> Self {
+        Self::batch(vec![action])
+    }
+
+    pub fn batch(actions: Vec<Action>) -> Self {
+        Effect {
+            actions,
+            #[cfg(feature = "futures")]
+            futures: Vec::new(),
+        }
+    }
+
+    /// An action produced asynchronously.
+    #[cfg(feature = "futures")]
+    pub fn future(future: impl core::future::Future<Output = Action> + 'static) -> Self {
+        Effect { actions: Vec::new(), futures: vec![future.boxed_local()] }
+    }
+
+    /// Both effects' actions, `self`'s first.
+    pub fn merge(mut self, other: Effect<Action>) -> Self {
+        self.actions.extend(other.actions);
+        #[cfg(feature = "futures")]
+        self.futures.extend(other.futures);
+        self
+    }
+
+    pub fn map<B: 'static>(self, f: fn(Action) -> B) -> Effect<B> {
+        Effect {
+            actions: self.actions.into_iter().map(f).collect(),
+            #[cfg(feature = "futures")]
+            futures: self.futures.into_iter().map(|future| future.map(f).boxed_local()).collect(),
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        #[cfg(feature = "futures")]
+        if !self.futures.is_empty() {
+            return false;
+        }
+        self.actions.is_empty()
+    }
+}
+
+// --- Reducers
+
+/// A boxed `Fn(&mut State, Action) -> Effect<Action>`.
+#[allow(clippy::type_complexity)]
+pub struct Reducer<State, Action>(Box<dyn Fn(&mut State, Action) -> Effect<Action>>);
+
+impl<State: 'static, Action: 'static> Reducer<State, Action> {
+    /// A reducer without follow-up actions.
+    pub fn new(f: impl Fn(&mut State, Action) + 'static) -> Self {
+        Reducer(Box::new(move |state: &mut State, action: Action| {
+            f(state, action);
+            Effect::none()
+        }))
+    }
+
+    /// A reducer that can return follow-up actions.
+    pub fn with_effects(f: impl Fn(&mut State, Action) -> Effect<Action> + 'static) -> Self {
+        Reducer(Box::new(f))
+    }
+
+    pub fn reduce(&self, state: &mut State, action: Action) -> Effect<Action> {
+        (self.0)(state, action)
+    }
+}
+
+/// Run every reducer on the same action, in order, merging their effects.
+pub fn combine<State: 'static, Action: Clone + 'static>(reducers: Vec<Reducer<State, Action>>) -> Reducer<State, Action> {
+    Reducer::with_effects(move |state: &mut State, action: Action| {
+        reducers
+            .iter()
+            .fold(Effect::none(), |effect, reducer| effect.merge(reducer.reduce(state, action.clone())))
+    })
+}
+
+/// Lift a reducer on a part of the state and a subset of actions to the whole app:
+/// `state` locates the part, `action` picks out the actions it handles and embeds the
+/// local effects back into app actions. Other actions are ignored.
+pub fn pullback<GlobalState, GlobalAction, LocalState, LocalAction>(
+    reducer: Reducer<LocalState, LocalAction>,
+    state: KeyPath<GlobalState, LocalState>,
+    action: CasePath<GlobalAction, LocalAction>,
+) -> Reducer<GlobalState, GlobalAction>
+where
+    GlobalState: 'static,
+    GlobalAction: 'static,
+    LocalState: Clone + 'static,
+    LocalAction: 'static,
+{
+    Reducer::with_effects(move |global: &mut GlobalState, global_action: GlobalAction| {
+        match (action.extract)(global_action) {
+            Some(local_action) => {
+                let mut local = (state.get)(global).clone();
+                let effect = reducer.reduce(&mut local, local_action);
+                (state.set)(global, local);
+                effect.map(action.embed)
+            }
+            None => Effect::none(),
+        }
+    })
+}
+
+// --- Middleware
+
+/// Runs before the reducer and can observe an action, replace it, or drop it by
+/// returning `None`.
+#[allow(clippy::type_complexity)]
+pub struct Middleware<State, Action>(Box<dyn Fn(&State, Action) -> Option<Action>>);
+
+impl<State: 'static, Action: 'static> Middleware<State, Action> {
+    pub fn new(f: impl Fn(&State, Action) -> Option<Action> + 'static) -> Self {
+        Middleware(Box::new(f))
+    }
+
+    pub fn run(&self, state: &State, action: Action) -> Option<Action> {
+        (self.0)(state, action)
+    }
+
+    /// Run `next` on whatever this middleware lets through.
+    pub fn then(self, next: Middleware<State, Action>) -> Self {
+        Middleware::new(move |state: &State, action: Action| next.run(state, self.run(state, action)?))
+    }
+}
+
+/// Report every action without changing it.
+pub fn logging<State: 'static, Action: 'static>(log: impl Fn(&State, &Action) + 'static) -> Middleware<State, Action> {
+    Middleware::new(move |state: &State, action: Action| {
+        log(state, &action);
+        Some(action)
+    })
+}
+
+/// Drop the actions for which `keep` is false.
+pub fn filtering<State: 'static, Action: 'static>(keep: impl Fn(&State, &Action) -> bool + 'static) -> Middleware<State, Action> {
+    Middleware::new(move |state: &State, action: Action| keep(state, &action).then_some(action))
+}
+
+// --- Store
+
+/// Holds the state and runs every sent action through the middleware, then the
+/// reducer, then sends the reducer's follow-up actions the same way.
+pub struct Store<State, Action> {
+    state: State,
+    reducer: Reducer<State, Action>,
+    middleware: Vec<Middleware<State, Action>>,
+    #[cfg(feature = "futures")]
+    pending: Vec<LocalBoxFuture<'static, Action>>,
+}
+
+impl<State: 'static, Action: 'static> Store<State, Action> {
+    pub fn new(state: State, reducer: Reducer<State, Action>) -> Self {
+        Self {
+            state,
+            reducer,
+            middleware: Vec::new(),
+            #[cfg(feature = "futures")]
+            pending: Vec::new(),
+        }
+    }
+
+    /// Add middleware; middleware runs in the order it was added.
+    pub fn with_middleware(mut self, middleware: Middleware<State, Action>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Process an action and, breadth first, every synchronous follow-up action.
+    pub fn send(&mut self, action: Action) {
+        let mut queue = VecDeque::from([action]);
+        while let Some(action) = queue.pop_front() {
+            let Some(action) = self.middleware.iter().try_fold(action, |action, m| m.run(&self.state, action)) else {
+                continue;
+            };
+            let effect = self.reducer.reduce(&mut self.state, action);
+            queue.extend(effect.actions);
+            #[cfg(feature = "futures")]
+            self.pending.extend(effect.futures);
+        }
+    }
+
+    /// Await the pending asynchronous effects and send their actions, until none are left.
+    #[cfg(feature = "futures")]
+    pub async fn settle(&mut self) {
+        while !self.pending.is_empty() {
+            let actions = futures::future::join_all(core::mem::take(&mut self.pending)).await;
+            for action in actions {
+                self.send(action);
+            }
+        }
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct Counter {
+        count: i32,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct App {
+        counter: Counter,
+        log: Vec<String>,
+    }
+
+    #[derive(Debug, Clone)]
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    #[derive(Debug, Clone)]
+    enum AppAction {
+        Counter(CounterAction),
+        Note(String),
+    }
+
+    fn counter_reducer() -> Reducer<Counter, CounterAction> {
+        Reducer::new(|state: &mut Counter, action| match action {
+            CounterAction::Increment => state.count += 1,
+            CounterAction::Decrement => state.count -= 1,
+        })
+    }
+
+    fn app_reducer() -> Reducer<App, AppAction> {
+        let counter = pullback(
+            counter_reducer(),
+            KeyPath::new(|a: &App| &a.counter, |a, c| a.counter = c),
+            CasePath::new(|a| if let AppAction::Counter(c) = a { Some(c) } else { None }, AppAction::Counter),
+        );
+        let log = Reducer::new(|state: &mut App, action: AppAction| {
+            if let AppAction::Note(note) = action {
+                state.log.push(note)
+            }
+        });
+        combine(vec![counter, log])
+    }
+
+    #[test]
+    fn test_pullback_and_combine() {
+        let reducer = app_reducer();
+        let mut app = App::default();
+        reducer.reduce(&mut app, AppAction::Counter(CounterAction::Increment));
+        reducer.reduce(&mut app, AppAction::Note("hello".into()));
+        reducer.reduce(&mut app, AppAction::Counter(CounterAction::Increment));
+        assert_eq!(app, App { counter: Counter { count: 2 }, log: vec!["hello".into()] });
+    }
+
+    #[test]
+    fn test_store() {
+        let mut store = Store::new(App::default(), app_reducer());
+        store.send(AppAction::Counter(CounterAction::Decrement));
+        assert_eq!(store.state().counter.count, -1);
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct Home {
+        temperature: i32,
+        heating: bool,
+        alerts: Vec<String>,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum HomeAction {
+        Reading(i32),
+        SetHeating(bool),
+        Alert(String),
+    }
+
+    fn home_reducer() -> Reducer<Home, HomeAction> {
+        Reducer::with_effects(|home: &mut Home, action| match action {
+            HomeAction::Reading(t) => {
+                home.temperature = t;
+                let heat = Effect::send(HomeAction::SetHeating(t < 18));
+                if t > 40 { heat.merge(Effect::send(HomeAction::Alert(format!("{t} degrees")))) } else { heat }
+            }
+            HomeAction::SetHeating(on) => {
+                home.heating = on;
+                Effect::none()
+            }
+            HomeAction::Alert(message) => {
+                home.alerts.push(message);
+                Effect::none()
+            }
+        })
+    }
+
+    #[test]
+    fn test_effects_send_follow_up_actions() {
+        let mut store = Store::new(Home::default(), home_reducer());
+        store.send(HomeAction::Reading(12));
+        assert!(store.state().heating);
+        store.send(HomeAction::Reading(45));
+        assert_eq!(store.state(), &Home { temperature: 45, heating: false, alerts: vec!["45 degrees".into()] });
+    }
+
+    #[test]
+    fn test_logging_and_filtering_middleware() {
+        let seen = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = seen.clone();
+        let mut store = Store::new(Home::default(), home_reducer())
+            .with_middleware(logging(move |_, action: &HomeAction| log.borrow_mut().push(action.clone())))
+            .with_middleware(filtering(|_, action| !matches!(action, HomeAction::Alert(_))));
+        store.send(HomeAction::Reading(50));
+        assert!(store.state().alerts.is_empty(), "alerts were filtered out");
+        assert_eq!(seen.borrow().len(), 3, "follow-up actions also pass through middleware");
+    }
+
+    #[test]
+    fn test_middleware_then_and_effect_map() {
+        let double = Middleware::new(|_: &Home, action| match action {
+            HomeAction::Reading(t) => Some(HomeAction::Reading(t * 2)),
+            other => Some(other),
+        });
+        let drop_heating = filtering(|_: &Home, action| !matches!(action, HomeAction::SetHeating(_)));
+        let both = double.then(drop_heating);
+        assert_eq!(both.run(&Home::default(), HomeAction::Reading(4)), Some(HomeAction::Reading(8)));
+        assert_eq!(both.run(&Home::default(), HomeAction::SetHeating(true)), None);
+        assert!(Effect::<i32>::none().map(|n| n + 1).is_none());
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_async_effects_settle() {
+        let reducer = Reducer::with_effects(|home: &mut Home, action| match action {
+            HomeAction::Reading(t) => {
+                home.temperature = t;
+                Effect::future(async move { HomeAction::SetHeating(t < 18) })
+            }
+            HomeAction::SetHeating(on) => {
+                home.heating = on;
+                Effect::none()
+            }
+            HomeAction::Alert(_) => Effect::none(),
+        });
+        let mut store = Store::new(Home::default(), reducer);
+        store.send(HomeAction::Reading(10));
+        assert!(!store.state().heating, "not applied until the effect completes");
+        futures::executor::block_on(store.settle());
+        assert!(store.state().heating);
+    }
+}
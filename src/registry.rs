@@ -0,0 +1,227 @@
+// Pipelines assembled at runtime: steps are registered under a name, and a list of names
+// (e.g. read from a configuration file) is turned into a single function. The types of
+// neighbouring steps are checked when the pipeline is built, not when it runs.
+
+use std::any::{Any, TypeId, type_name};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::func::{BoxFn, Func};
+use crate::try_pipeline::{BoxTryFn, TryPipeline};
+
+type AnyFn = Rc<dyn Fn(Box<dyn Any>) -> Box<dyn Any>>;
+type SharedFn<A, B> = Rc<dyn Fn(A) -> B>;
+
+struct Step {
+    input: (TypeId, &'static str),
+    output: (TypeId, &'static str),
+    f: AnyFn,
+}
+
+/// Why a list of step names could not be built into a pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    UnknownStep(String),
+    /// `step` takes `expected`, but the previous step (or the pipeline input) gives `found`.
+    /// For the pipeline output `step` is `None`.
+    TypeMismatch { step: Option<String>, expected: &'static str, found: &'static str },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::UnknownStep(name) => write!(f, "unknown step `{name}`"),
+            RegistryError::TypeMismatch { step: Some(step), expected, found } => {
+                write!(f, "step `{step}` takes {expected}, but is given {found}")
+            }
+            RegistryError::TypeMismatch { step: None, expected, found } => {
+                write!(f, "pipeline should return {expected}, but returns {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// Named steps of any types, assembled into pipelines with `build`.
+#[derive(Default)]
+pub struct Registry {
+    steps: HashMap<String, Step>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `f` under `name`, replacing any step of the same name.
+    pub fn register<A: 'static, B: 'static>(&mut self, name: impl Into<String>, f: impl Fn(A) -> B + 'static) -> &mut Self {
+        let step = Step {
+            input: (TypeId::of::<A>(), type_name::<A>()),
+            output: (TypeId::of::<B>(), type_name::<B>()),
+            f: Rc::new(move |a: Box<dyn Any>| Box::new(f(*a.downcast::<A>().expect("input type checked by build"))) as Box<dyn Any>),
+        };
+        self.steps.insert(name.into(), step);
+        self
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.steps.contains_key(name)
+    }
+
+    /// Chain the named steps in order into a function `(A) -> B`. No names is the
+    /// identity, so then `A` and `B` must be the same type.
+    pub fn build<A: 'static, B: 'static>(&self, names: &[&str]) -> Result<BoxFn<A, B>, RegistryError> {
+        let mut current = (TypeId::of::<A>(), type_name::<A>());
+        let mut fs = Vec::with_capacity(names.len());
+        for &name in names {
+            let step = self.steps.get(name).ok_or_else(|| RegistryError::UnknownStep(name.to_string()))?;
+            if step.input.0 != current.0 {
+                return Err(RegistryError::TypeMismatch { step: Some(name.to_string()), expected: step.input.1, found: current.1 });
+            }
+            current = step.output;
+            fs.push(step.f.clone());
+        }
+        if current.0 != TypeId::of::<B>() {
+            return Err(RegistryError::TypeMismatch { step: None, expected: type_name::<B>(), found: current.1 });
+        }
+        Ok(Func::new(move |a: A| {
+            let out = fs.iter().fold(Box::new(a) as Box<dyn Any>, |value, f| f(value));
+            *out.downcast::<B>().expect("output type checked by build")
+        })
+        .boxed())
+    }
+}
+
+/// Functions of one type `(A) -> B` registered by key, e.g. validation profiles selected
+/// per message. Unlike `Registry` no type checks are needed, so lookups cannot fail on
+/// types, only on missing keys.
+pub struct FnRegistry<K, A, B> {
+    fns: HashMap<K, SharedFn<A, B>>,
+}
+
+impl<K: Eq + Hash, A: 'static, B: 'static> FnRegistry<K, A, B> {
+    pub fn new() -> Self {
+        Self { fns: HashMap::new() }
+    }
+
+    /// Register `f` under `key`, replacing any function with the same key.
+    pub fn register(&mut self, key: K, f: impl Fn(A) -> B + 'static) -> &mut Self {
+        self.fns.insert(key, Rc::new(f));
+        self
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.fns.contains_key(key)
+    }
+
+    pub fn get(&self, key: &K) -> Option<BoxFn<A, B>> {
+        let f = self.fns.get(key)?.clone();
+        Some(Func::new(move |a: A| f(a)).boxed())
+    }
+
+    fn lookup(&self, keys: impl IntoIterator<Item = K>) -> Result<Vec<SharedFn<A, B>>, K> {
+        keys.into_iter().map(|k| self.fns.get(&k).cloned().ok_or(k)).collect()
+    }
+}
+
+impl<K: Eq + Hash, A: 'static, B: 'static> Default for FnRegistry<K, A, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, A: 'static> FnRegistry<K, A, A> {
+    /// The functions for `keys` run in order, or the first key that is not registered.
+    pub fn compose_keys(&self, keys: impl IntoIterator<Item = K>) -> Result<BoxFn<A, A>, K> {
+        let fs = self.lookup(keys)?;
+        Ok(Func::new(move |a: A| fs.iter().fold(a, |a, f| f(a))).boxed())
+    }
+}
+
+impl<K: Eq + Hash, A: 'static, E: 'static> FnRegistry<K, A, Result<A, E>> {
+    /// `compose_keys` for throwing functions: stops at the first error.
+    pub fn try_compose_keys(&self, keys: impl IntoIterator<Item = K>) -> Result<BoxTryFn<A, A, E>, K> {
+        let fs = self.lookup(keys)?;
+        Ok(TryPipeline::from_fn(move |a: A| fs.iter().try_fold(a, |a, f| f(a))).boxed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> Registry {
+        let mut r = Registry::new();
+        r.register("trim", |s: String| s.trim().to_string())
+            .register("upper", |s: String| s.to_uppercase())
+            .register("length", |s: String| s.len())
+            .register("double", |n: usize| n * 2);
+        r
+    }
+
+    #[test]
+    fn test_build_from_names() {
+        let r = registry();
+        let config = "trim, upper";
+        let names: Vec<&str> = config.split(',').map(str::trim).collect();
+        let normalize = r.build::<String, String>(&names).unwrap();
+        assert_eq!(normalize.call("  eur ".to_string()), "EUR");
+        let size = r.build::<String, usize>(&["trim", "length", "double"]).unwrap();
+        assert_eq!(size.call(" ab ".to_string()), 4);
+        assert_eq!(r.build::<u8, u8>(&[]).unwrap().call(7), 7);
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let r = registry();
+        assert_eq!(r.build::<String, String>(&["trim", "reverse"]).err(), Some(RegistryError::UnknownStep("reverse".to_string())));
+        let err = r.build::<String, usize>(&["length", "upper"]).err().unwrap();
+        assert_eq!(err.to_string(), format!("step `upper` takes {}, but is given usize", type_name::<String>()));
+        assert_eq!(
+            r.build::<String, String>(&["length"]).err(),
+            Some(RegistryError::TypeMismatch { step: None, expected: type_name::<String>(), found: type_name::<usize>() })
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Payment {
+        amount: u64,
+        currency: &'static str,
+        reference: String,
+    }
+
+    fn profiles() -> FnRegistry<&'static str, Payment, Result<Payment, String>> {
+        let mut r = FnRegistry::new();
+        r.register("eur_only", |p: Payment| if p.currency == "EUR" { Ok(p) } else { Err(format!("{} not allowed", p.currency)) })
+            .register("usd_only", |p: Payment| if p.currency == "USD" { Ok(p) } else { Err(format!("{} not allowed", p.currency)) })
+            .register("reference_140", |mut p: Payment| {
+                p.reference.truncate(140);
+                Ok(p)
+            })
+            .register("positive", |p: Payment| if p.amount > 0 { Ok(p) } else { Err("zero amount".to_string()) });
+        r
+    }
+
+    #[test]
+    fn test_try_compose_keys_per_profile() {
+        let r = profiles();
+        let sepa = r.try_compose_keys(["eur_only", "positive", "reference_140"]).unwrap();
+        let us_wire = r.try_compose_keys(["usd_only", "positive"]).unwrap();
+        let payment = Payment { amount: 10, currency: "EUR", reference: "x".repeat(200) };
+        assert_eq!(sepa.run(payment.clone()).map(|p| p.reference.len()), Ok(140));
+        assert_eq!(us_wire.run(payment), Err("EUR not allowed".to_string()));
+        assert_eq!(r.try_compose_keys(["eur_only", "iban"]).err(), Some("iban"));
+    }
+
+    #[test]
+    fn test_compose_keys_and_get() {
+        let mut r = FnRegistry::new();
+        r.register("inc", |n: i32| n + 1).register("double", |n: i32| n * 2);
+        assert_eq!(r.compose_keys(["inc", "double", "inc"]).unwrap().call(1), 5);
+        assert_eq!(r.get(&"double").map(|f| f.call(4)), Some(8));
+        assert!(r.get(&"halve").is_none());
+    }
+}
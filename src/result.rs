@@ -0,0 +1,408 @@
+// Helpers for throwing functions (Swift `throws` → Rust `Result`).
+
+use core::fmt;
+
+use crate::functor;
+use crate::prelude::*;
+use crate::uncurry::CurriedStep;
+
+/// End a throwing pipeline: turn every error into a value with `handler`,
+/// giving an infallible `(A) -> B`.
+pub fn recover<A, B, E, F, H>(f: F, handler: H) -> impl Fn(A) -> B
+where
+    F: Fn(A) -> Result<B, E>,
+    H: Fn(E) -> B,
+{
+    move |a: A| f(a).unwrap_or_else(&handler)
+}
+
+/// Try `f`, and if it fails try the alternative `g` on the same input.
+/// The error of `f` is discarded; `g`'s error is reported if both fail.
+pub fn catch<A, B, E1, E2, F, G>(f: F, g: G) -> impl Fn(A) -> Result<B, E2>
+where
+    A: Clone,
+    F: Fn(A) -> Result<B, E1>,
+    G: Fn(A) -> Result<B, E2>,
+{
+    move |a: A| f(a.clone()).or_else(|_| g(a))
+}
+
+/// Turn a collection of results into a result of a collection, stopping at the first error.
+pub fn sequence<T, E>(results: impl IntoIterator<Item = Result<T, E>>) -> Result<Vec<T>, E> {
+    results.into_iter().collect()
+}
+
+/// Run a throwing function over every element, stopping at the first error.
+pub fn traverse<A, B, E, F>(items: impl IntoIterator<Item = A>, f: F) -> Result<Vec<B>, E>
+where
+    F: FnMut(A) -> Result<B, E>,
+{
+    items.into_iter().map(f).collect()
+}
+
+// liftN: turn an ordinary `(A, B, ...) -> R` into one over results. The plain versions stop
+// at the first error; the `_accumulating` versions report the errors of every argument.
+macro_rules! lift_result {
+    ($name:ident, $acc:ident, $($a:ident: $A:ident),+) => {
+        pub fn $name<$($A,)+ R, Err, F>(f: F) -> impl Fn($(Result<$A, Err>),+) -> Result<R, Err>
+        where
+            F: Fn($($A),+) -> R,
+        {
+            move |$($a: Result<$A, Err>),+| Ok(f($($a?),+))
+        }
+
+        lift_result!(@accumulating $acc, $($a: $A),+);
+    };
+    (@accumulating $acc:ident, $($a:ident: $A:ident),+) => {
+        pub fn $acc<$($A,)+ R, Err, F>(f: F) -> impl Fn($(Result<$A, Err>),+) -> Result<R, Vec<Err>>
+        where
+            F: Fn($($A),+) -> R,
+        {
+            move |$($a: Result<$A, Err>),+| {
+                let mut errors = Vec::new();
+                $(let $a = $a.map_err(|e| errors.push(e)).ok();)+
+                match ($($a,)+) {
+                    ($(Some($a),)+) => Ok(f($($a),+)),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+}
+
+/// `lift2` is the generic applicative one from `functor`, specialised to `Result`.
+pub fn lift2<A, B, R, Err, F>(f: F) -> impl Fn(Result<A, Err>, Result<B, Err>) -> Result<R, Err>
+where
+    F: Fn(A, B) -> R,
+{
+    functor::lift2(f)
+}
+
+lift_result!(@accumulating lift2_accumulating, a: A, b: B);
+lift_result!(lift3, lift3_accumulating, a: A, b: B, c: C);
+lift_result!(lift4, lift4_accumulating, a: A, b: B, c: C, d: D);
+lift_result!(lift5, lift5_accumulating, a: A, b: B, c: C, d: D, e: E);
+lift_result!(lift6, lift6_accumulating, a: A, b: B, c: C, d: D, e: E, g: G);
+lift_result!(lift7, lift7_accumulating, a: A, b: B, c: C, d: D, e: E, g: G, h: H);
+lift_result!(lift8, lift8_accumulating, a: A, b: B, c: C, d: D, e: E, g: G, h: H, i: I);
+lift_result!(lift9, lift9_accumulating, a: A, b: B, c: C, d: D, e: E, g: G, h: H, i: I, j: J);
+lift_result!(lift10, lift10_accumulating, a: A, b: B, c: C, d: D, e: E, g: G, h: H, i: I, j: J, k: K);
+
+// zipN: pair up results, reporting the first error in argument order.
+macro_rules! zip_result {
+    ($name:ident, $($a:ident: $A:ident),+) => {
+        #[allow(clippy::too_many_arguments)]
+        pub fn $name<$($A,)+ Err>($($a: Result<$A, Err>),+) -> Result<($($A,)+), Err> {
+            Ok(($($a?,)+))
+        }
+    };
+}
+
+arities!(zip_result; _, zip2, zip3, zip4, zip5, zip6, zip7, zip8, zip9, zip10, zip11, zip12, zip13, zip14, zip15, zip16);
+
+/// Applicative `apply`: call a wrapped function with a wrapped argument, reporting the
+/// function's error first. Like the Option version this accepts curried functions, so
+/// `Ok(curry2(f)).apply(a).apply(b)` feeds fallible arguments one at a time.
+pub fn apply<A, E, F>(f: Result<F, E>, a: Result<A, E>) -> Result<F::Output, E>
+where
+    F: CurriedStep<A>,
+{
+    Ok(f?.apply(a?))
+}
+
+/// Method form of [`apply`].
+pub trait Apply<A, E> {
+    type Output;
+
+    fn apply(self, a: Result<A, E>) -> Result<Self::Output, E>;
+}
+
+impl<A, E, F> Apply<A, E> for Result<F, E>
+where
+    F: CurriedStep<A>,
+{
+    type Output = F::Output;
+
+    fn apply(self, a: Result<A, E>) -> Result<F::Output, E> {
+        apply(self, a)
+    }
+}
+
+/// Map the success and the error channel in one step.
+pub fn bimap<T, U, E1, E2, F, G>(f: F, g: G) -> impl Fn(Result<T, E1>) -> Result<U, E2>
+where
+    F: Fn(T) -> U,
+    G: Fn(E1) -> E2,
+{
+    move |r: Result<T, E1>| r.map(&f).map_err(&g)
+}
+
+/// Same as [`bimap`], under the name used by other libraries.
+pub fn map_both<T, U, E1, E2, F, G>(f: F, g: G) -> impl Fn(Result<T, E1>) -> Result<U, E2>
+where
+    F: Fn(T) -> U,
+    G: Fn(E1) -> E2,
+{
+    bimap(f, g)
+}
+
+/// Exchange the channels: `Ok` becomes `Err` and the other way round.
+pub fn swap_result<T, E>(r: Result<T, E>) -> Result<E, T> {
+    match r {
+        Ok(t) => Err(t),
+        Err(e) => Ok(e),
+    }
+}
+
+/// Remove one level of nesting, e.g. after mapping with a throwing function.
+pub fn flatten_result<T, E>(r: Result<Result<T, E>, E>) -> Result<T, E> {
+    r.and_then(|inner| inner)
+}
+
+/// Convert the error of a throwing function once, at the boundary of a pipeline, instead
+/// of with a `map_err` inside every stage.
+pub fn map_err_fn<A, B, E1, E2, F, G>(f: F, convert: G) -> impl Fn(A) -> Result<B, E2>
+where
+    F: Fn(A) -> Result<B, E1>,
+    G: Fn(E1) -> E2,
+{
+    move |a: A| f(a).map_err(&convert)
+}
+
+/// Method form of [`map_err_fn`] for any throwing function, e.g. a whole
+/// `chain3_throwing(...)`: `pipeline.map_err(ApiError::from)`.
+pub trait ThrowingFn<A, B, E>: Fn(A) -> Result<B, E> + Sized {
+    fn map_err<E2, G>(self, convert: G) -> impl Fn(A) -> Result<B, E2>
+    where
+        G: Fn(E) -> E2,
+    {
+        map_err_fn(self, convert)
+    }
+}
+
+impl<A, B, E, F> ThrowingFn<A, B, E> for F where F: Fn(A) -> Result<B, E> {}
+
+/// An error together with a description of what was being done when it happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contextual<E> {
+    pub context: String,
+    pub error: E,
+}
+
+impl<E: fmt::Display> fmt::Display for Contextual<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.context, self.error)
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for Contextual<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Label the errors of a throwing stage, so a failure in a long chain says which stage
+/// produced it.
+pub fn context<A, B, E, F>(msg: impl Into<String>, f: F) -> impl Fn(A) -> Result<B, Contextual<E>>
+where
+    F: Fn(A) -> Result<B, E>,
+{
+    let context = msg.into();
+    move |a: A| f(a).map_err(|error| Contextual { context: context.clone(), error })
+}
+
+/// [`context`] with a message that is only built when the stage fails.
+pub fn with_context<A, B, E, F, M>(msg: M, f: F) -> impl Fn(A) -> Result<B, Contextual<E>>
+where
+    F: Fn(A) -> Result<B, E>,
+    M: Fn() -> String,
+{
+    move |a: A| f(a).map_err(|error| Contextual { context: msg(), error })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::{chain2_throwing, chain3_throwing};
+    use crate::curry::curry2;
+
+    #[test]
+    fn test_recover() {
+        let parse_or_zero = recover(|s: &str| s.parse::<i32>(), |_| 0);
+        assert_eq!(parse_or_zero("12"), 12);
+        assert_eq!(parse_or_zero("twelve"), 0);
+    }
+
+    #[test]
+    fn test_recover_ends_throwing_chain() {
+        let pipeline = recover(
+            chain2_throwing(
+                |s: &str| s.parse::<i32>().map_err(|e| e.to_string()),
+                |n: i32| if n > 0 { Ok(n) } else { Err(format!("{} is not positive", n)) },
+            ),
+            |_: String| 1,
+        );
+        assert_eq!(pipeline("5"), 5);
+        assert_eq!(pipeline("-5"), 1);
+        assert_eq!(pipeline("x"), 1);
+    }
+
+    #[test]
+    fn test_catch_uses_alternative() {
+        let parse_int_or_float = catch(
+            |s: &str| s.parse::<i64>(),
+            |s: &str| s.parse::<f64>().map(|f| f.round() as i64),
+        );
+        assert_eq!(parse_int_or_float("7"), Ok(7));
+        assert_eq!(parse_int_or_float("7.6"), Ok(8));
+        assert!(parse_int_or_float("seven").is_err());
+    }
+
+    #[test]
+    fn test_sequence() {
+        assert_eq!(sequence(vec![Ok::<_, &str>(1), Ok(2)]), Ok(vec![1, 2]));
+        assert_eq!(sequence(vec![Ok(1), Err("first"), Err("second")]), Err("first"));
+    }
+
+    #[test]
+    fn test_traverse_parses_codes() {
+        let parse = |s: &str| s.parse::<u16>().map_err(|_| format!("invalid code {s}"));
+        assert_eq!(traverse(["978", "840"], parse), Ok(vec![978, 840]));
+        assert_eq!(traverse(["978", "EUR"], parse), Err("invalid code EUR".to_string()));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Transfer {
+        amount: u32,
+        currency: String,
+    }
+
+    fn parse_amount(s: &str) -> Result<u32, String> {
+        s.parse().map_err(|_| format!("invalid amount {s}"))
+    }
+
+    fn parse_currency(s: &str) -> Result<String, String> {
+        if s.len() == 3 { Ok(s.to_string()) } else { Err(format!("invalid currency {s}")) }
+    }
+
+    #[test]
+    fn test_lift2_short_circuits() {
+        let transfer = lift2(|amount, currency| Transfer { amount, currency });
+        assert_eq!(
+            transfer(parse_amount("10"), parse_currency("EUR")),
+            Ok(Transfer { amount: 10, currency: "EUR".to_string() })
+        );
+        assert_eq!(transfer(parse_amount("x"), parse_currency("EURO")), Err("invalid amount x".to_string()));
+    }
+
+    #[test]
+    fn test_lift2_accumulating_reports_all_errors() {
+        let transfer = lift2_accumulating(|amount, currency| Transfer { amount, currency });
+        assert_eq!(
+            transfer(parse_amount("x"), parse_currency("EURO")),
+            Err(vec!["invalid amount x".to_string(), "invalid currency EURO".to_string()])
+        );
+        assert!(transfer(parse_amount("1"), parse_currency("USD")).is_ok());
+    }
+
+    #[test]
+    fn test_lift10_accumulating() {
+        let sum = lift10_accumulating(|a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8, i: u8, j: u8| {
+            a + b + c + d + e + f + g + h + i + j
+        });
+        let ok = || Ok::<u8, usize>(1);
+        assert_eq!(sum(ok(), ok(), ok(), ok(), ok(), ok(), ok(), ok(), ok(), ok()), Ok(10));
+        assert_eq!(sum(Err(0), ok(), ok(), ok(), ok(), ok(), ok(), ok(), ok(), Err(9)), Err(vec![0, 9]));
+    }
+
+    #[test]
+    fn test_apply_with_curried_function() {
+        let transfer = curry2(|amount: u32, currency: String| format!("{amount} {currency}"));
+        assert_eq!(Ok(&transfer).apply(parse_amount("10")).apply(parse_currency("EUR")), Ok("10 EUR".to_string()));
+        assert_eq!(
+            Ok(&transfer).apply(parse_amount("x")).apply(parse_currency("EUR")),
+            Err("invalid amount x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_reports_function_error_first() {
+        let f: Result<fn(i32) -> i32, &str> = Err("no function");
+        assert_eq!(apply(f, Err("no argument")), Err("no function"));
+    }
+
+    #[test]
+    fn test_bimap() {
+        let describe = bimap(|n: u32| n * 2, |e: std::num::ParseIntError| format!("bad input: {e}"));
+        assert_eq!(describe("21".parse()), Ok(42));
+        assert_eq!(describe("x".parse()), Err("bad input: invalid digit found in string".to_string()));
+    }
+
+    #[test]
+    fn test_context_names_the_failing_stage() {
+        let pipeline = chain3_throwing(
+            context("parse amount", |s: &str| s.parse::<i64>().map_err(|e| e.to_string())),
+            context("check limit", |n: i64| if n <= 1000 { Ok(n) } else { Err(format!("{n} over limit")) }),
+            with_context(|| "convert to cents".to_string(), |n: i64| n.checked_mul(100).ok_or_else(|| "overflow".to_string())),
+        );
+        let ok: Result<i64, Contextual<String>> = pipeline("12");
+        assert_eq!(ok, Ok(1200));
+        assert_eq!(pipeline("5000").unwrap_err().to_string(), "check limit: 5000 over limit");
+        assert_eq!(pipeline("x").unwrap_err().context, "parse amount");
+    }
+
+    #[test]
+    fn test_contextual_error_source() {
+        use std::error::Error;
+        let err = context("read port", |s: &str| s.parse::<u16>())("99999").unwrap_err();
+        assert_eq!(err.source().unwrap().to_string(), "number too large to fit in target type");
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum ApiError {
+        BadRequest(String),
+    }
+
+    fn positive(n: i32) -> Result<i32, String> {
+        if n > 0 { Ok(n) } else { Err(format!("{n} is not positive")) }
+    }
+
+    #[test]
+    fn test_map_err_fn_at_the_boundary() {
+        let handler = map_err_fn(
+            chain2_throwing(|s: &str| s.parse::<i32>().map_err(|e| e.to_string()), positive),
+            ApiError::BadRequest,
+        );
+        assert_eq!(handler("3"), Ok(3));
+        assert_eq!(handler("-3"), Err(ApiError::BadRequest("-3 is not positive".to_string())));
+    }
+
+    #[test]
+    fn test_map_err_method() {
+        let handler = positive.map_err(ApiError::BadRequest);
+        assert_eq!(handler(0), Err(ApiError::BadRequest("0 is not positive".to_string())));
+    }
+
+    #[test]
+    fn test_zip_reports_first_error() {
+        assert_eq!(zip2(Ok::<_, &str>(1), Ok('a')), Ok((1, 'a')));
+        assert_eq!(zip3(Ok::<i32, &str>(1), Err::<char, _>("second"), Err::<u8, _>("third")), Err("second"));
+        let ok = |n: u32| Ok::<u32, String>(n);
+        let z = zip16(ok(0), ok(1), ok(2), ok(3), ok(4), ok(5), ok(6), ok(7), ok(8), ok(9), ok(10), ok(11), ok(12), ok(13), ok(14), ok(15));
+        assert_eq!(z.map(|t| t.0 + t.15), Ok(15));
+    }
+
+    #[test]
+    fn test_map_both_swap_and_flatten() {
+        let describe = map_both(|n: i32| n * 2, |e: &str| e.len());
+        assert_eq!(describe(Ok(2)), Ok(4));
+        assert_eq!(describe(Err("bad")), Err(3));
+        assert_eq!(swap_result(Ok::<i32, &str>(1)), Err(1));
+        assert_eq!(swap_result(Err::<i32, &str>("e")), Ok("e"));
+        let parse = |s: &str| s.parse::<i32>().map_err(|e| e.to_string());
+        let read = |s: &'static str| Ok::<_, String>(s);
+        assert_eq!(flatten_result(read("7").map(parse)), Ok(7));
+        assert!(flatten_result(read("x").map(parse)).is_err());
+        assert_eq!(flatten_result(Err::<Result<i32, _>, _>("outer".to_string())), Err("outer".to_string()));
+    }
+}
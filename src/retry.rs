@@ -0,0 +1,187 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::thread;
+use std::time::Duration;
+
+/// How long to wait between attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// The same delay before every retry.
+    Fixed(Duration),
+    /// `initial * factor^(retry - 1)`, capped at `max`.
+    Exponential { initial: Duration, factor: u32, max: Duration },
+}
+
+/// When and how often `retry` re-invokes a failing function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff: Backoff,
+    jitter: bool,
+}
+
+impl RetryPolicy {
+    /// Retry after a constant delay, 3 attempts in total by default.
+    pub fn fixed(delay: Duration) -> Self {
+        Self { max_attempts: 3, backoff: Backoff::Fixed(delay), jitter: false }
+    }
+
+    /// Retry with delays doubling from `initial` up to `max`, 3 attempts in total by default.
+    pub fn exponential(initial: Duration, max: Duration) -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Backoff::Exponential { initial, factor: 2, max },
+            jitter: false,
+        }
+    }
+
+    /// Total number of calls, including the first one. Values below 1 are treated as 1.
+    pub fn max_attempts(self, max_attempts: u32) -> Self {
+        Self { max_attempts: max_attempts.max(1), ..self }
+    }
+
+    /// Randomize each delay to between 50% and 100% of its nominal value,
+    /// so many clients retrying together don't hit a service in lockstep.
+    pub fn with_jitter(self) -> Self {
+        Self { jitter: true, ..self }
+    }
+
+    /// Nominal delay before retry number `retry` (1 for the first retry), without jitter.
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        match self.backoff {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { initial, factor, max } => {
+                let multiplier = factor.saturating_pow(retry.saturating_sub(1));
+                initial.saturating_mul(multiplier).min(max)
+            }
+        }
+    }
+
+    fn sleep_before(&self, retry: u32, sleep: &impl Fn(Duration)) {
+        let mut delay = self.delay_for(retry);
+        if self.jitter {
+            delay = jittered(delay);
+        }
+        if !delay.is_zero() {
+            sleep(delay);
+        }
+    }
+}
+
+fn jittered(delay: Duration) -> Duration {
+    // `RandomState` is randomly seeded per instance, which is enough for spreading retries.
+    let random = RandomState::new().build_hasher().finish();
+    let fraction = 0.5 + (random % 1_000) as f64 / 2_000.0;
+    delay.mul_f64(fraction)
+}
+
+/// Re-invoke `f` according to `policy` until it succeeds or attempts run out,
+/// returning the last error. The input is cloned for every attempt.
+/// Waits with `thread::sleep`, so it is not available on `wasm32-unknown-unknown`.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn retry<A, B, E, F>(policy: RetryPolicy, f: F) -> impl Fn(A) -> Result<B, E>
+where
+    A: Clone,
+    F: Fn(A) -> Result<B, E>,
+{
+    retry_with_sleep(policy, thread::sleep, f)
+}
+
+/// `retry`, waiting between attempts with `sleep` instead of `thread::sleep`
+/// (a no-op skips the delays, e.g. where blocking is impossible).
+pub fn retry_with_sleep<A, B, E, F, S>(policy: RetryPolicy, sleep: S, f: F) -> impl Fn(A) -> Result<B, E>
+where
+    A: Clone,
+    F: Fn(A) -> Result<B, E>,
+    S: Fn(Duration),
+{
+    move |a: A| {
+        let mut attempt = 1;
+        loop {
+            match f(a.clone()) {
+                Ok(b) => return Ok(b),
+                Err(e) if attempt >= policy.max_attempts => return Err(e),
+                Err(_) => {
+                    policy.sleep_before(attempt, &sleep);
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compose::compose2_res;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_retry_until_success() {
+        let calls = Cell::new(0);
+        let flaky = |x: i32| {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 { Err("busy") } else { Ok(x * 2) }
+        };
+        let reliable = retry(RetryPolicy::fixed(Duration::ZERO).max_attempts(5), flaky);
+        assert_eq!(reliable(21), Ok(42));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_retry_gives_up_with_last_error() {
+        let calls = Cell::new(0);
+        let always_fails = |_: ()| {
+            calls.set(calls.get() + 1);
+            Err::<(), _>(format!("failure {}", calls.get()))
+        };
+        let f = retry(RetryPolicy::fixed(Duration::ZERO).max_attempts(4), always_fails);
+        assert_eq!(f(()), Err("failure 4".to_string()));
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[test]
+    fn test_exponential_delays() {
+        let policy = RetryPolicy::exponential(Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(policy.delay_for(1), Duration::from_millis(100));
+        assert_eq!(policy.delay_for(2), Duration::from_millis(200));
+        assert_eq!(policy.delay_for(4), Duration::from_millis(800));
+        assert_eq!(policy.delay_for(5), Duration::from_secs(1));
+        assert_eq!(policy.delay_for(64), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let delay = Duration::from_millis(100);
+        for _ in 0..100 {
+            let d = jittered(delay);
+            assert!(d >= Duration::from_millis(50) && d <= delay, "{:?}", d);
+        }
+    }
+
+    #[test]
+    fn test_retry_composes_with_throwing_pipelines() {
+        let calls = Cell::new(0);
+        let fetch = |id: u32| {
+            calls.set(calls.get() + 1);
+            if calls.get() == 1 { Err("timeout") } else { Ok(id * 10) }
+        };
+        let validate = |n: u32| if n > 0 { Ok(n) } else { Err("empty") };
+        let pipeline = compose2_res(validate, retry(RetryPolicy::fixed(Duration::ZERO).with_jitter(), fetch));
+        let result: Result<u32, &str> = pipeline(7);
+        assert_eq!(result, Ok(70));
+    }
+
+    #[test]
+    fn test_retry_with_sleep_reports_delays() {
+        let delays = std::cell::RefCell::new(Vec::new());
+        let policy = RetryPolicy::exponential(Duration::from_millis(100), Duration::from_secs(1)).max_attempts(4);
+        let f = retry_with_sleep(policy, |d| delays.borrow_mut().push(d), |_: ()| Err::<(), _>("offline"));
+        assert_eq!(f(()), Err("offline"));
+        assert_eq!(
+            *delays.borrow(),
+            vec![Duration::from_millis(100), Duration::from_millis(200), Duration::from_millis(400)]
+        );
+    }
+}
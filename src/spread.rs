@@ -0,0 +1,84 @@
+// Adapters between multi-argument functions and functions of one tuple, so the tuples
+// produced by `zipN` can feed ordinary functions directly.
+// spread(f)((a, b, c)) == f(a, b, c)
+// gather3(g)(a, b, c) == g((a, b, c))
+
+/// A function that can be called with its arguments packed in a tuple. Implemented for
+/// every `Fn` of 2 to 10 arguments, with `Args` the tuple of argument types.
+pub trait Spread<Args> {
+    type Output;
+    fn apply(&self, args: Args) -> Self::Output;
+}
+
+/// Turn `Fn(A, B, ...) -> R` into `Fn((A, B, ...)) -> R`, for 2 to 10 arguments.
+pub fn spread<Args, F>(function: F) -> impl Fn(Args) -> F::Output
+where
+    F: Spread<Args>,
+{
+    move |args: Args| function.apply(args)
+}
+
+// The inverse needs one function per arity, since the arity of the result varies;
+// `gather` is the two-argument one, as with `flip`.
+macro_rules! spread_gather {
+    ($name:ident, $($a:ident: $A:ident),+) => {
+        impl<$($A,)+ R, F> Spread<($($A,)+)> for F
+        where
+            F: Fn($($A),+) -> R,
+        {
+            type Output = R;
+            fn apply(&self, ($($a,)+): ($($A,)+)) -> R {
+                self($($a),+)
+            }
+        }
+
+        /// Turn `Fn((A, B, ...)) -> R` into `Fn(A, B, ...) -> R`.
+        pub fn $name<$($A,)+ R, F>(function: F) -> impl Fn($($A),+) -> R
+        where
+            F: Fn(($($A,)+)) -> R,
+        {
+            move |$($a: $A),+| function(($($a,)+))
+        }
+    };
+}
+
+arities!(spread_gather; _, gather, gather3, gather4, gather5, gather6, gather7, gather8, gather9, gather10);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options;
+
+    fn full_name(first: &str, middle: Option<char>, last: &str) -> String {
+        match middle {
+            Some(initial) => format!("{} {}. {}", first, initial, last),
+            None => format!("{} {}", first, last),
+        }
+    }
+
+    #[test]
+    fn test_spread_feeds_zip_output() {
+        let name = options::zip3(Some("Ada"), Some(Some('K')), Some("Lovelace")).map(spread(full_name));
+        assert_eq!(name, Some("Ada K. Lovelace".to_string()));
+        let pairs = vec![(1, 2), (3, 4)];
+        let sums: Vec<i32> = pairs.into_iter().map(spread(|a: i32, b: i32| a + b)).collect();
+        assert_eq!(sums, vec![3, 7]);
+    }
+
+    #[test]
+    fn test_spread_ten_arguments() {
+        let sum10 = |a: u8, b: u8, c: u8, d: u8, e: u8, g: u8, h: u8, i: u8, j: u8, k: u8| a + b + c + d + e + g + h + i + j + k;
+        assert_eq!(spread(sum10)((1, 2, 3, 4, 5, 6, 7, 8, 9, 10)), 55);
+    }
+
+    #[test]
+    fn test_gather_is_inverse_of_spread() {
+        let area = |(w, h): (u32, u32)| w * h;
+        assert_eq!(gather(area)(3, 4), 12);
+        assert_eq!(spread(gather(area))((3, 4)), 12);
+        let volume = gather3(|(w, h, d): (u32, u32, u32)| w * h * d);
+        assert_eq!(volume(2, 3, 4), 24);
+        let first = gather10(|t: (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8)| t.0);
+        assert_eq!(first(9, 0, 0, 0, 0, 0, 0, 0, 0, 0), 9);
+    }
+}
@@ -0,0 +1,1101 @@
+// Combinators over whole sequences: products, transposes and other ways of combining
+// several collections at once.
+
+use alloc::collections::VecDeque;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::prelude::*;
+use crate::comparator::{KeyPaths, comparing_by_keypath, comparing_by_keys};
+use crate::either::Either;
+use crate::keypath::KeyPath;
+
+// --- Filtering
+
+/// The elements satisfying `predicate`, e.g. a `Predicate::into_fn()`.
+pub fn filter<I, P>(items: I, predicate: P) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    items.into_iter().filter(predicate).collect()
+}
+
+// --- Cartesian products
+
+/// Every pair `(a, b)`, produced lazily with `a` varying slowest.
+/// The second sequence is iterated once per element of the first, so its iterator must be `Clone`.
+pub fn cartesian_product2<IA, IB>(a: IA, b: IB) -> impl Iterator<Item = (IA::Item, IB::Item)> + Clone
+where
+    IA: IntoIterator,
+    IA::IntoIter: Clone,
+    IA::Item: Clone,
+    IB: IntoIterator,
+    IB::IntoIter: Clone,
+{
+    let b = b.into_iter();
+    a.into_iter().flat_map(move |x| b.clone().map(move |y| (x.clone(), y)))
+}
+
+/// Every triple `(a, b, c)`, produced lazily.
+pub fn cartesian_product3<IA, IB, IC>(a: IA, b: IB, c: IC) -> impl Iterator<Item = (IA::Item, IB::Item, IC::Item)> + Clone
+where
+    IA: IntoIterator,
+    IA::IntoIter: Clone,
+    IA::Item: Clone,
+    IB: IntoIterator,
+    IB::IntoIter: Clone,
+    IB::Item: Clone,
+    IC: IntoIterator,
+    IC::IntoIter: Clone,
+{
+    cartesian_product2(cartesian_product2(a, b), c).map(|((a, b), c)| (a, b, c))
+}
+
+/// Every quadruple `(a, b, c, d)`, produced lazily.
+#[allow(clippy::type_complexity)]
+pub fn cartesian_product4<IA, IB, IC, ID>(
+    a: IA,
+    b: IB,
+    c: IC,
+    d: ID,
+) -> impl Iterator<Item = (IA::Item, IB::Item, IC::Item, ID::Item)> + Clone
+where
+    IA: IntoIterator,
+    IA::IntoIter: Clone,
+    IA::Item: Clone,
+    IB: IntoIterator,
+    IB::IntoIter: Clone,
+    IB::Item: Clone,
+    IC: IntoIterator,
+    IC::IntoIter: Clone,
+    IC::Item: Clone,
+    ID: IntoIterator,
+    ID::IntoIter: Clone,
+{
+    cartesian_product2(cartesian_product3(a, b, c), d).map(|((a, b, c), d)| (a, b, c, d))
+}
+
+/// Combine every pair with `f`, lazily.
+pub fn cartesian_with<IA, IB, R, F>(f: F, a: IA, b: IB) -> impl Iterator<Item = R>
+where
+    IA: IntoIterator,
+    IA::IntoIter: Clone,
+    IA::Item: Clone,
+    IB: IntoIterator,
+    IB::IntoIter: Clone,
+    F: Fn(IA::Item, IB::Item) -> R,
+{
+    cartesian_product2(a, b).map(move |(a, b)| f(a, b))
+}
+
+// --- Transpose
+//
+// Ragged rows: column `i` holds the `i`-th element of every row long enough to have
+// one, so short rows are skipped rather than truncating the whole result. Rows of
+// equal length transpose exactly; `transpose(transpose(m)) == m` only holds for those.
+
+/// Rows to columns.
+pub fn transpose<T>(rows: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    transpose_lazy(rows).collect()
+}
+
+/// Rows to columns, one column per `next()`. Each row is an iterator that is advanced
+/// once per column, so huge or streaming rows are never collected up front.
+pub fn transpose_lazy<R>(rows: impl IntoIterator<Item = R>) -> impl Iterator<Item = Vec<R::Item>>
+where
+    R: IntoIterator,
+{
+    let mut rows: Vec<R::IntoIter> = rows.into_iter().map(IntoIterator::into_iter).collect();
+    core::iter::from_fn(move || {
+        let column: Vec<R::Item> = rows.iter_mut().filter_map(Iterator::next).collect();
+        if column.is_empty() { None } else { Some(column) }
+    })
+}
+
+// --- Indexed zips
+//
+// zipN_with_index: walk several sequences in lockstep, stopping at the shortest, and
+// pass the position along with the elements so messages can say "transaction #3".
+
+macro_rules! zip_with_index {
+    ($name:ident, $($a:ident: $I:ident),+) => {
+        #[allow(clippy::too_many_arguments)]
+        pub fn $name<$($I,)+ R, F>(mut f: F, $($a: $I),+) -> Vec<R>
+        where
+            $($I: IntoIterator,)+
+            F: FnMut(usize, $($I::Item),+) -> R,
+        {
+            $(let mut $a = $a.into_iter();)+
+            let mut out = Vec::new();
+            for index in 0.. {
+                $(let Some($a) = $a.next() else { break };)+
+                out.push(f(index, $($a),+));
+            }
+            out
+        }
+    };
+}
+
+arities!(
+    zip_with_index;
+    zip1_with_index, zip2_with_index, zip3_with_index, zip4_with_index, zip5_with_index, zip6_with_index,
+    zip7_with_index, zip8_with_index, zip9_with_index, zip10_with_index, zip11_with_index, zip12_with_index,
+    zip13_with_index, zip14_with_index, zip15_with_index, zip16_with_index,
+);
+
+// --- Map joins
+//
+// Join maps on the keys present in all of them (an inner join); keys missing from any
+// map are dropped. `zip_maps` joins two maps, `zip_maps3`..`zip_maps16` more.
+
+macro_rules! zip_maps {
+    ($name:ident, $first:ident: $A:ident, $($m:ident: $M:ident),+) => {
+        #[cfg(feature = "std")]
+        #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+        pub fn $name<Key, $A, $($M),+>($first: HashMap<Key, $A>, $(mut $m: HashMap<Key, $M>),+) -> HashMap<Key, ($A, $($M),+)>
+        where
+            Key: Eq + Hash,
+        {
+            $first
+                .into_iter()
+                .filter_map(|(k, $first)| {
+                    $(let $m = $m.remove(&k)?;)+
+                    Some((k, ($first, $($m),+)))
+                })
+                .collect()
+        }
+    };
+}
+
+macro_rules! zip_maps_with {
+    ($name:ident, $first:ident: $A:ident, $($m:ident: $M:ident),+) => {
+        #[cfg(feature = "std")]
+        #[allow(clippy::too_many_arguments)]
+        pub fn $name<Key, $A, $($M,)+ R, F>(f: F, $first: HashMap<Key, $A>, $(mut $m: HashMap<Key, $M>),+) -> HashMap<Key, R>
+        where
+            Key: Eq + Hash,
+            F: Fn($A, $($M),+) -> R,
+        {
+            $first
+                .into_iter()
+                .filter_map(|(k, $first)| {
+                    $(let $m = $m.remove(&k)?;)+
+                    Some((k, f($first, $($m),+)))
+                })
+                .collect()
+        }
+    };
+}
+
+arities!(
+    zip_maps;
+    _, zip_maps, zip_maps3, zip_maps4, zip_maps5, zip_maps6, zip_maps7, zip_maps8, zip_maps9,
+    zip_maps10, zip_maps11, zip_maps12, zip_maps13, zip_maps14, zip_maps15, zip_maps16,
+);
+arities!(
+    zip_maps_with;
+    _, zip_maps_with, zip_maps3_with, zip_maps4_with, zip_maps5_with, zip_maps6_with, zip_maps7_with,
+    zip_maps8_with, zip_maps9_with, zip_maps10_with, zip_maps11_with, zip_maps12_with, zip_maps13_with,
+    zip_maps14_with, zip_maps15_with, zip_maps16_with,
+);
+
+// --- Windows and chunks
+//
+// Like the slice methods of the same name, but over any `IntoIterator`. A size of 0
+// panics, as it does for `slice::windows`.
+
+/// Overlapping windows of `size` consecutive elements; none if there are fewer than `size`.
+pub fn windows<I>(items: I, size: usize) -> Vec<Vec<I::Item>>
+where
+    I: IntoIterator,
+    I::Item: Clone,
+{
+    windows_lazy(items, size).collect()
+}
+
+/// Lazy [`windows`]: only `size` elements are buffered at a time.
+pub fn windows_lazy<I>(items: I, size: usize) -> impl Iterator<Item = Vec<I::Item>>
+where
+    I: IntoIterator,
+    I::Item: Clone,
+{
+    assert!(size > 0, "window size must be non-zero");
+    let mut items = items.into_iter();
+    let mut window = VecDeque::with_capacity(size);
+    core::iter::from_fn(move || {
+        while window.len() < size {
+            window.push_back(items.next()?);
+        }
+        let out = window.iter().cloned().collect();
+        window.pop_front();
+        Some(out)
+    })
+}
+
+/// Consecutive non-overlapping chunks of `size` elements; the last one may be shorter.
+pub fn chunks<I: IntoIterator>(items: I, size: usize) -> Vec<Vec<I::Item>> {
+    chunks_lazy(items, size).collect()
+}
+
+/// Lazy [`chunks`].
+pub fn chunks_lazy<I: IntoIterator>(items: I, size: usize) -> impl Iterator<Item = Vec<I::Item>> {
+    assert!(size > 0, "chunk size must be non-zero");
+    let mut items = items.into_iter();
+    core::iter::from_fn(move || {
+        let chunk: Vec<I::Item> = items.by_ref().take(size).collect();
+        if chunk.is_empty() { None } else { Some(chunk) }
+    })
+}
+
+/// Apply `f` to every window of `size` elements. The window is borrowed, so unlike
+/// [`windows`] the elements don't need to be `Clone`.
+pub fn sliding_map<I, R, F>(items: I, size: usize, mut f: F) -> Vec<R>
+where
+    I: IntoIterator,
+    F: FnMut(&[I::Item]) -> R,
+{
+    assert!(size > 0, "window size must be non-zero");
+    let mut window = VecDeque::with_capacity(size);
+    let mut out = Vec::new();
+    for item in items {
+        window.push_back(item);
+        if window.len() == size {
+            out.push(f(window.make_contiguous()));
+            window.pop_front();
+        }
+    }
+    out
+}
+
+// --- Running accumulations
+
+/// The accumulator after every element, starting from `init` (which itself is not included).
+pub fn scan<I, B, F>(items: I, init: B, mut f: F) -> Vec<B>
+where
+    I: IntoIterator,
+    B: Clone,
+    F: FnMut(B, I::Item) -> B,
+{
+    let mut acc = init;
+    items
+        .into_iter()
+        .map(|item| {
+            acc = f(acc.clone(), item);
+            acc.clone()
+        })
+        .collect()
+}
+
+/// Fold using the first element as the initial value; `None` for an empty sequence.
+pub fn fold1<I, F>(items: I, f: F) -> Option<I::Item>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item, I::Item) -> I::Item,
+{
+    items.into_iter().reduce(f)
+}
+
+/// Every intermediate result of [`fold1`], e.g. running totals; the last one is `fold1`'s result.
+pub fn reductions<I, F>(items: I, mut f: F) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Clone,
+    F: FnMut(I::Item, I::Item) -> I::Item,
+{
+    let mut items = items.into_iter();
+    match items.next() {
+        Some(first) => {
+            let mut out = vec![first.clone()];
+            out.extend(scan(items, first, &mut f));
+            out
+        }
+        None => Vec::new(),
+    }
+}
+
+// --- Grouping
+
+/// Group elements by key; within a group, elements keep their original order.
+#[cfg(feature = "std")]
+pub fn group_by<I, K, F>(items: I, mut key: F) -> HashMap<K, Vec<I::Item>>
+where
+    I: IntoIterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    let mut groups: HashMap<K, Vec<I::Item>> = HashMap::new();
+    for item in items {
+        groups.entry(key(&item)).or_default().push(item);
+    }
+    groups
+}
+
+/// [`group_by`] on the value a keypath points at.
+#[cfg(feature = "std")]
+pub fn group_by_keypath<I, K>(items: I, keypath: KeyPath<I::Item, K>) -> HashMap<K, Vec<I::Item>>
+where
+    I: IntoIterator,
+    K: Eq + Hash + Clone,
+{
+    group_by(items, |item| (keypath.get)(item).clone())
+}
+
+/// Like [`group_by`], but the groups are returned in the order their keys first appear.
+#[cfg(feature = "std")]
+pub fn group_by_ordered<I, K, F>(items: I, mut key: F) -> Vec<(K, Vec<I::Item>)>
+where
+    I: IntoIterator,
+    K: Eq + Hash + Clone,
+    F: FnMut(&I::Item) -> K,
+{
+    let mut index: HashMap<K, usize> = HashMap::new();
+    let mut groups: Vec<(K, Vec<I::Item>)> = Vec::new();
+    for item in items {
+        let k = key(&item);
+        let i = *index.entry(k.clone()).or_insert_with(|| {
+            groups.push((k, Vec::new()));
+            groups.len() - 1
+        });
+        groups[i].1.push(item);
+    }
+    groups
+}
+
+/// Look-up table keyed by `key`. If several elements share a key, the last one wins;
+/// use [`index_by_many`] to keep them all.
+#[cfg(feature = "std")]
+pub fn index_by<I, K, F>(items: I, mut key: F) -> HashMap<K, I::Item>
+where
+    I: IntoIterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    items.into_iter().map(|item| (key(&item), item)).collect()
+}
+
+/// [`index_by`] on the value a keypath points at.
+#[cfg(feature = "std")]
+pub fn index_by_keypath<I, K>(items: I, keypath: KeyPath<I::Item, K>) -> HashMap<K, I::Item>
+where
+    I: IntoIterator,
+    K: Eq + Hash + Clone,
+{
+    index_by(items, |item| (keypath.get)(item).clone())
+}
+
+/// Look-up table keeping every element per key: [`group_by`] under its look-up name
+/// (see [`group_by_keypath`] for the keypath form).
+#[cfg(feature = "std")]
+pub fn index_by_many<I, K, F>(items: I, key: F) -> HashMap<K, Vec<I::Item>>
+where
+    I: IntoIterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    group_by(items, key)
+}
+
+// --- Partitioning
+
+/// Split a sequence in one pass: `f` sends each element left or right, possibly
+/// transforming it on the way. Either side can be any collection that can be extended.
+pub fn partition_map<I, L, R, CL, CR, F>(items: I, mut f: F) -> (CL, CR)
+where
+    I: IntoIterator,
+    CL: Default + Extend<L>,
+    CR: Default + Extend<R>,
+    F: FnMut(I::Item) -> Either<L, R>,
+{
+    let mut lefts = CL::default();
+    let mut rights = CR::default();
+    for item in items {
+        match f(item) {
+            Either::Left(l) => lefts.extend(Some(l)),
+            Either::Right(r) => rights.extend(Some(r)),
+        }
+    }
+    (lefts, rights)
+}
+
+// --- Intersperse and interleave
+
+/// Put `separator` between every two elements.
+pub fn intersperse<I>(items: I, separator: I::Item) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Clone,
+{
+    intersperse_lazy(items, separator).collect()
+}
+
+/// Lazy [`intersperse`].
+pub fn intersperse_lazy<I>(items: I, separator: I::Item) -> impl Iterator<Item = I::Item>
+where
+    I: IntoIterator,
+    I::Item: Clone,
+{
+    let mut items = items.into_iter().peekable();
+    let mut separator_next = false;
+    core::iter::from_fn(move || {
+        items.peek()?;
+        separator_next = !separator_next;
+        if separator_next { items.next() } else { Some(separator.clone()) }
+    })
+}
+
+/// Alternate elements of `a` and `b`, starting with `a`. When one runs out, the rest of
+/// the other follows.
+pub fn interleave<IA, IB>(a: IA, b: IB) -> Vec<IA::Item>
+where
+    IA: IntoIterator,
+    IB: IntoIterator<Item = IA::Item>,
+{
+    interleave_lazy(a, b).collect()
+}
+
+/// Lazy [`interleave`].
+pub fn interleave_lazy<IA, IB>(a: IA, b: IB) -> impl Iterator<Item = IA::Item>
+where
+    IA: IntoIterator,
+    IB: IntoIterator<Item = IA::Item>,
+{
+    let mut a = a.into_iter().fuse();
+    let mut b = b.into_iter().fuse();
+    let mut from_a = false;
+    core::iter::from_fn(move || {
+        from_a = !from_a;
+        if from_a { a.next().or_else(|| b.next()) } else { b.next().or_else(|| a.next()) }
+    })
+}
+
+// --- Prefixes
+
+/// The longest prefix whose elements all satisfy `predicate`.
+pub fn take_while<I, P>(items: I, predicate: P) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    items.into_iter().take_while(predicate).collect()
+}
+
+/// Everything after the longest prefix satisfying `predicate`.
+pub fn drop_while<I, P>(items: I, predicate: P) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    items.into_iter().skip_while(predicate).collect()
+}
+
+/// `(take_while, drop_while)` in a single pass.
+pub fn span<I, P>(items: I, mut predicate: P) -> (Vec<I::Item>, Vec<I::Item>)
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    let mut items = items.into_iter();
+    let mut prefix = Vec::new();
+    for item in items.by_ref() {
+        if !predicate(&item) {
+            let mut rest = vec![item];
+            rest.extend(items);
+            return (prefix, rest);
+        }
+        prefix.push(item);
+    }
+    (prefix, Vec::new())
+}
+
+// --- In-place mutation (like Swift Overture's `mver` for sequences)
+
+/// Update every element in place.
+pub fn mut_each<T>(items: &mut [T], f: impl FnMut(&mut T)) {
+    items.iter_mut().for_each(f)
+}
+
+/// Keep only the elements satisfying `predicate`, in place.
+pub fn mut_filter<T>(items: &mut Vec<T>, predicate: impl FnMut(&T) -> bool) {
+    items.retain(predicate)
+}
+
+/// Replace every element with `f`'s result, dropping those for which it returns `None`.
+/// The vector's allocation is reused.
+pub fn mut_retain_map<T>(items: &mut Vec<T>, f: impl FnMut(T) -> Option<T>) {
+    *items = core::mem::take(items).into_iter().filter_map(f).collect();
+}
+
+// --- Sorting by keypaths (stable)
+
+/// The elements sorted by the value a keypath points at.
+pub fn sorted_by_keypath<I, V>(items: I, keypath: KeyPath<I::Item, V>) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    V: Ord,
+{
+    let mut v: Vec<I::Item> = items.into_iter().collect();
+    sort_by_keypath(&mut v, keypath);
+    v
+}
+
+/// In-place [`sorted_by_keypath`].
+pub fn sort_by_keypath<T, V: Ord>(items: &mut [T], keypath: KeyPath<T, V>) {
+    items.sort_by(comparing_by_keypath(keypath).into_fn())
+}
+
+/// The elements sorted by a tuple of keypaths, e.g. `(department, salary)`.
+pub fn sorted_by_keys<I, K>(items: I, keys: K) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    K: KeyPaths<I::Item>,
+{
+    let mut v: Vec<I::Item> = items.into_iter().collect();
+    sort_by_keys(&mut v, keys);
+    v
+}
+
+/// In-place [`sorted_by_keys`].
+pub fn sort_by_keys<T, K: KeyPaths<T>>(items: &mut [T], keys: K) {
+    items.sort_by(comparing_by_keys(keys).into_fn())
+}
+
+// --- Aggregation
+//
+// The `by` function picks the number out of each element; a keypath works via
+// `keypath::get(kp)`.
+
+/// The sum of `f` over all elements.
+pub fn sum_by<I, N, F>(items: I, f: F) -> N
+where
+    I: IntoIterator,
+    N: core::iter::Sum<N>,
+    F: FnMut(I::Item) -> N,
+{
+    items.into_iter().map(f).sum()
+}
+
+/// The arithmetic mean of `f` over all elements; `None` for an empty sequence.
+pub fn average_by<I, F>(items: I, mut f: F) -> Option<f64>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> f64,
+{
+    let (sum, count) = items.into_iter().fold((0.0, 0usize), |(sum, count), item| (sum + f(item), count + 1));
+    if count == 0 { None } else { Some(sum / count as f64) }
+}
+
+/// How many elements satisfy `predicate`.
+pub fn count_by<I, P>(items: I, mut predicate: P) -> usize
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    items.into_iter().filter(|item| predicate(item)).count()
+}
+
+/// The `p`-th percentile (0 to 100) of `f` over all elements, interpolating linearly
+/// between the two nearest ranks; `None` for an empty sequence. Panics if `p` is
+/// outside 0..=100.
+pub fn percentile_by<I, F>(items: I, p: f64, f: F) -> Option<f64>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> f64,
+{
+    assert!((0.0..=100.0).contains(&p), "percentile must be between 0 and 100");
+    let mut values: Vec<f64> = items.into_iter().map(f).collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    // `rank` is non-negative, so truncating is `floor`; done by hand as `f64::floor`
+    // needs std.
+    let rank = p / 100.0 * (values.len() - 1) as f64;
+    let below = rank as usize;
+    let fraction = rank - below as f64;
+    let (lower, upper) = (values[below], values[if fraction > 0.0 { below + 1 } else { below }]);
+    Some(lower + (upper - lower) * fraction)
+}
+
+// --- Parallel versions (`rayon` feature)
+//
+// Same shape as the sequential functions, but `f` runs on rayon's thread pool, so it
+// must be `Sync + Send` and the elements `Send`. Output order matches input order.
+
+#[cfg(feature = "rayon")]
+pub use self::par::*;
+
+#[cfg(feature = "rayon")]
+mod par {
+    use rayon::prelude::*;
+
+    pub fn par_map<I, R, F>(items: I, f: F) -> Vec<R>
+    where
+        I: IntoParallelIterator,
+        R: Send,
+        F: Fn(I::Item) -> R + Sync + Send,
+    {
+        items.into_par_iter().map(f).collect()
+    }
+
+    pub fn par_filter<I, P>(items: I, predicate: P) -> Vec<I::Item>
+    where
+        I: IntoParallelIterator,
+        P: Fn(&I::Item) -> bool + Sync + Send,
+    {
+        items.into_par_iter().filter(predicate).collect()
+    }
+
+    /// Parallel [`fold1`](super::fold1). `f` must be associative, since elements are
+    /// combined in an unspecified grouping.
+    pub fn par_reduce<I, F>(items: I, f: F) -> Option<I::Item>
+    where
+        I: IntoParallelIterator,
+        F: Fn(I::Item, I::Item) -> I::Item + Sync + Send,
+    {
+        items.into_par_iter().reduce_with(f)
+    }
+}
+
+// --- Async streams (`futures` feature)
+
+/// Stream versions of the suites adapters: same argument order and plain (non-async)
+/// transforms, but over `futures::Stream` and returning streams.
+#[cfg(feature = "futures")]
+pub mod stream {
+    use futures::future::ready;
+    use futures::stream::unfold;
+    use futures::{Stream, StreamExt};
+
+    pub fn map<S, R, F>(stream: S, f: F) -> impl Stream<Item = R>
+    where
+        S: Stream,
+        F: FnMut(S::Item) -> R,
+    {
+        stream.map(f)
+    }
+
+    pub fn filter_map<S, R, F>(stream: S, mut f: F) -> impl Stream<Item = R>
+    where
+        S: Stream,
+        F: FnMut(S::Item) -> Option<R>,
+    {
+        stream.filter_map(move |item| ready(f(item)))
+    }
+
+    // zip (two streams), zip3..zip16: pair items up, ending with the shortest stream.
+    // Each item is awaited from the streams in argument order; they are pinned once, up
+    // front, so any stream can be passed.
+    macro_rules! zip_stream {
+        ($name:ident, $($a:ident: $A:ident),+) => {
+            #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+            pub fn $name<$($A),+>($($a: $A),+) -> impl Stream<Item = ($($A::Item,)+)>
+            where
+                $($A: Stream,)+
+            {
+                unfold(($(Box::pin($a),)+), |($(mut $a,)+)| async move {
+                    let items = ($($a.next().await?,)+);
+                    Some((items, ($($a,)+)))
+                })
+            }
+        };
+    }
+
+    arities!(zip_stream; _, zip, zip3, zip4, zip5, zip6, zip7, zip8, zip9, zip10, zip11, zip12, zip13, zip14, zip15, zip16);
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use futures::executor::block_on;
+        use futures::stream::{iter, repeat};
+
+        #[test]
+        fn test_map_and_filter_map() {
+            let readings = filter_map(iter(["1", "x", "3"]), |s| s.parse::<i32>().ok());
+            let doubled: Vec<i32> = block_on(map(readings, |n| n * 2).collect());
+            assert_eq!(doubled, vec![2, 6]);
+        }
+
+        #[test]
+        fn test_zip6_ends_with_shortest() {
+            let zipped = zip6(iter([1, 2]), iter("ab".chars()), repeat(true), iter([0.5]), repeat(()), iter(["x", "y"]));
+            let items: Vec<_> = block_on(zipped.collect());
+            assert_eq!(items, vec![(1, 'a', true, 0.5, (), "x")]);
+        }
+
+        #[test]
+        fn test_zip16() {
+            let s = || iter([1, 2]);
+            let zipped = zip16(s(), s(), s(), s(), s(), s(), s(), s(), s(), s(), s(), s(), s(), s(), s(), iter([9]));
+            let items: Vec<_> = block_on(zipped.collect());
+            assert_eq!(items.len(), 1);
+            assert_eq!((items[0].0, items[0].14, items[0].15), (1, 1, 9));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cartesian_product2_order() {
+        let pairs: Vec<_> = cartesian_product2([1, 2], ['a', 'b']).collect();
+        assert_eq!(pairs, vec![(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+        assert_eq!(cartesian_product2([1, 2], Vec::<char>::new()).count(), 0);
+    }
+
+    #[test]
+    fn test_cartesian_product3_test_matrix() {
+        let matrix: Vec<_> = cartesian_product3(["linux", "macos"], ["stable", "nightly"], [false, true]).collect();
+        assert_eq!(matrix.len(), 8);
+        assert_eq!(matrix[0], ("linux", "stable", false));
+        assert_eq!(matrix[7], ("macos", "nightly", true));
+    }
+
+    #[test]
+    fn test_cartesian_product4_is_lazy() {
+        let mut it = cartesian_product4(0.., [1, 2], [3], [4, 5]);
+        assert_eq!(it.next(), Some((0, 1, 3, 4)));
+        assert_eq!(it.nth(3), Some((1, 1, 3, 4)));
+    }
+
+    #[test]
+    fn test_cartesian_with() {
+        let sums: Vec<i32> = cartesian_with(|a, b| a * 10 + b, [1, 2], [3, 4]).collect();
+        assert_eq!(sums, vec![13, 14, 23, 24]);
+    }
+
+    #[test]
+    fn test_transpose_square() {
+        let m = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(transpose(m.clone()), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(transpose(transpose(m.clone())), m);
+    }
+
+    #[test]
+    fn test_transpose_ragged_skips_short_rows() {
+        let m = vec![vec![1, 2, 3], vec![4], vec![5, 6]];
+        assert_eq!(transpose(m), vec![vec![1, 4, 5], vec![2, 6], vec![3]]);
+        assert_eq!(transpose(Vec::<Vec<i32>>::new()), Vec::<Vec<i32>>::new());
+    }
+
+    #[test]
+    fn test_transpose_lazy_columns() {
+        let readings = [0.., 100..];
+        let mut columns = transpose_lazy(readings);
+        assert_eq!(columns.next(), Some(vec![0, 100]));
+        assert_eq!(columns.next(), Some(vec![1, 101]));
+    }
+
+    #[test]
+    fn test_zip2_with_index_reports_positions() {
+        let amounts = [10, -5, 20, -1];
+        let accounts = ["a", "b", "c", "d"];
+        let errors: Vec<String> = zip2_with_index(
+            |i, amount: i32, account| (amount < 0).then(|| format!("transaction #{i} ({account}) invalid")),
+            amounts,
+            accounts,
+        )
+        .into_iter()
+        .flatten()
+        .collect();
+        assert_eq!(errors, vec!["transaction #1 (b) invalid", "transaction #3 (d) invalid"]);
+    }
+
+    #[test]
+    fn test_zip3_with_index_stops_at_shortest() {
+        let rows = zip3_with_index(|i, a, b, c| (i, a, b, c), [1, 2, 3], "xy".chars(), [true, false, true]);
+        assert_eq!(rows, vec![(0, 1, 'x', true), (1, 2, 'y', false)]);
+    }
+
+    #[test]
+    fn test_zip1_with_index() {
+        assert_eq!(zip1_with_index(|i, s: &str| format!("{i}:{s}"), ["a", "b"]), vec!["0:a", "1:b"]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_zip_maps_inner_join() {
+        let celsius = HashMap::from([("s1", 20.0), ("s2", 25.0), ("s3", 30.0)]);
+        let humidity = HashMap::from([("s1", 40), ("s3", 55), ("s4", 60)]);
+        let joined = zip_maps(celsius, humidity);
+        assert_eq!(joined, HashMap::from([("s1", (20.0, 40)), ("s3", (30.0, 55))]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_zip_maps3_with_balances() {
+        let opening = HashMap::from([(1, 100), (2, 50)]);
+        let credits = HashMap::from([(1, 30), (2, 0)]);
+        let debits = HashMap::from([(1, 10), (3, 5)]);
+        let closing = zip_maps3_with(|o, c, d| o + c - d, opening, credits, debits);
+        assert_eq!(closing, HashMap::from([(1, 120)]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_zip_maps4() {
+        let m = |v: char| HashMap::from([("k", v)]);
+        assert_eq!(zip_maps4(m('a'), m('b'), m('c'), m('d'))["k"], ('a', 'b', 'c', 'd'));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_zip_maps16_with() {
+        let m = |v: u32| HashMap::from([("k", v), ("only-some", v)]);
+        let mut last = m(16);
+        last.remove("only-some");
+        let total = zip_maps16_with(
+            |a, b, c, d, e, g, h, i, j, k, l, n, o, p, q, s| a + b + c + d + e + g + h + i + j + k + l + n + o + p + q + s,
+            m(1), m(2), m(3), m(4), m(5), m(6), m(7), m(8), m(9), m(10), m(11), m(12), m(13), m(14), m(15), last,
+        );
+        assert_eq!(total, HashMap::from([("k", 136)]));
+    }
+
+    #[test]
+    fn test_windows() {
+        assert_eq!(windows([1, 2, 3, 4], 3), vec![vec![1, 2, 3], vec![2, 3, 4]]);
+        assert!(windows([1, 2], 3).is_empty());
+        assert_eq!(windows_lazy(1.., 2).nth(10), Some(vec![11, 12]));
+    }
+
+    #[test]
+    fn test_chunks() {
+        assert_eq!(chunks(1..=5, 2), vec![vec![1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(chunks_lazy(0.., 3).next(), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn test_sliding_map_velocity() {
+        // Transactions per sliding hour: (minute, amount) pairs, windows of 3 consecutive.
+        let transactions = vec![(0, 10), (20, 500), (40, 700), (150, 20)];
+        let flagged = sliding_map(transactions, 3, |w| {
+            let within_hour = w[w.len() - 1].0 - w[0].0 <= 60;
+            within_hour && w.iter().map(|(_, amount)| amount).sum::<i32>() > 1000
+        });
+        assert_eq!(flagged, vec![true, false]);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero")]
+    fn test_windows_zero_size_panics() {
+        windows([1], 0);
+    }
+
+    #[test]
+    fn test_scan_running_balance() {
+        let balances = scan([100, -30, 50], 1000, |balance, delta| balance + delta);
+        assert_eq!(balances, vec![1100, 1070, 1120]);
+        assert!(scan(Vec::<i32>::new(), 0, |a, b| a + b).is_empty());
+    }
+
+    #[test]
+    fn test_fold1() {
+        assert_eq!(fold1([3, 9, 4], i32::max), Some(9));
+        assert_eq!(fold1(Vec::<i32>::new(), i32::max), None);
+    }
+
+    #[test]
+    fn test_reductions_control_sum() {
+        let amounts = [10, 20, 30];
+        let running = reductions(amounts, |a, b| a + b);
+        assert_eq!(running, vec![10, 30, 60]);
+        assert_eq!(running.last().copied(), fold1(amounts, |a, b| a + b));
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Transaction {
+        user: String,
+        amount: u32,
+    }
+
+    fn tx(user: &str, amount: u32) -> Transaction {
+        Transaction { user: user.to_string(), amount }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_group_by() {
+        let groups = group_by([1, 2, 3, 4, 5], |n| n % 2 == 0);
+        assert_eq!(groups[&true], vec![2, 4]);
+        assert_eq!(groups[&false], vec![1, 3, 5]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_group_by_keypath() {
+        let user = KeyPath::new(|t: &Transaction| &t.user, |t, user| t.user = user);
+        let groups = group_by_keypath(vec![tx("ann", 1), tx("bob", 2), tx("ann", 3)], user);
+        assert_eq!(groups["ann"], vec![tx("ann", 1), tx("ann", 3)]);
+        assert_eq!(groups.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_group_by_ordered_keeps_first_seen_order() {
+        let groups = group_by_ordered(vec![tx("bob", 1), tx("ann", 2), tx("bob", 3)], |t| t.user.clone());
+        let summary: Vec<(String, u32)> = groups.into_iter().map(|(u, ts)| (u, ts.iter().map(|t| t.amount).sum())).collect();
+        assert_eq!(summary, vec![("bob".to_string(), 4), ("ann".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_partition_map_classifies() {
+        let (suspicious, ok): (Vec<String>, Vec<u32>) = partition_map(vec![tx("ann", 50), tx("bob", 9000)], |t| {
+            if t.amount > 1000 { Either::Left(t.user) } else { Either::Right(t.amount) }
+        });
+        assert_eq!(suspicious, vec!["bob".to_string()]);
+        assert_eq!(ok, vec![50]);
+    }
+
+    #[test]
+    fn test_partition_map_from_results() {
+        use std::collections::HashMap;
+        let (errors, values): (Vec<String>, HashMap<&str, i32>) = partition_map(["a=1", "b", "c=3"], |s| {
+            match s.split_once('=') {
+                Some((k, v)) => Either::Right((k, v.parse().unwrap())),
+                None => Either::Left(format!("missing value for {s}")),
+            }
+        });
+        assert_eq!(errors, vec!["missing value for b".to_string()]);
+        assert_eq!(values, HashMap::from([("a", 1), ("c", 3)]));
+    }
+
+    #[test]
+    fn test_intersperse() {
+        assert_eq!(intersperse(["a", "b", "c"], ", ").concat(), "a, b, c");
+        assert_eq!(intersperse([1], 0), vec![1]);
+        assert!(intersperse(Vec::<i32>::new(), 0).is_empty());
+    }
+
+    #[test]
+    fn test_intersperse_lazy_on_infinite() {
+        let first: Vec<i32> = intersperse_lazy(1.., 0).take(5).collect();
+        assert_eq!(first, vec![1, 0, 2, 0, 3]);
+    }
+
+    #[test]
+    fn test_interleave_merges_streams() {
+        assert_eq!(interleave([1, 3, 5, 7], [2, 4]), vec![1, 2, 3, 4, 5, 7]);
+        assert_eq!(interleave(Vec::new(), ["b"]), vec!["b"]);
+        let merged: Vec<i32> = interleave_lazy(0.., [100]).take(4).collect();
+        assert_eq!(merged, vec![0, 100, 1, 2]);
+    }
+
+    #[test]
+    fn test_take_and_drop_while() {
+        let log = ["INFO a", "INFO b", "ERROR c", "INFO d"];
+        let is_info = |line: &&str| line.starts_with("INFO");
+        assert_eq!(take_while(log, is_info), vec!["INFO a", "INFO b"]);
+        assert_eq!(drop_while(log, is_info), vec!["ERROR c", "INFO d"]);
+    }
+
+    #[test]
+    fn test_span() {
+        assert_eq!(span([1, 2, 5, 1], |n| *n < 3), (vec![1, 2], vec![5, 1]));
+        assert_eq!(span([1, 2], |n| *n < 3), (vec![1, 2], vec![]));
+        assert_eq!(span([5, 1], |n| *n < 3), (vec![], vec![5, 1]));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Device {
+        name: &'static str,
+        on: bool,
+        battery: u8,
+    }
+
+    #[test]
+    fn test_mut_each_updates_devices() {
+        let mut devices = vec![Device { name: "lamp", on: false, battery: 90 }, Device { name: "fan", on: false, battery: 5 }];
+        mut_each(&mut devices, |d| d.on = d.battery > 10);
+        assert_eq!(devices.iter().map(|d| d.on).collect::<Vec<_>>(), vec![true, false]);
+    }
+
+    #[test]
+    fn test_mut_filter_and_retain_map() {
+        let mut devices = vec![Device { name: "lamp", on: true, battery: 90 }, Device { name: "fan", on: false, battery: 0 }];
+        mut_retain_map(&mut devices, |d| (d.battery > 0).then(|| Device { battery: d.battery - 1, ..d }));
+        assert_eq!(devices, vec![Device { name: "lamp", on: true, battery: 89 }]);
+
+        let mut ns = vec![1, 2, 3, 4];
+        mut_filter(&mut ns, |n| n % 2 == 0);
+        assert_eq!(ns, vec![2, 4]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_map_filter_reduce() {
+        let scores = par_map((0..10_000).collect::<Vec<u64>>(), |n| n % 97);
+        assert_eq!(scores[..3], [0, 1, 2]);
+        let flagged = par_filter(scores, |s| *s > 95);
+        assert_eq!(flagged.len(), 103);
+        assert_eq!(par_reduce(flagged, |a, b| a + b), Some(103 * 96));
+        assert_eq!(par_reduce(Vec::<u64>::new(), |a, b| a + b), None);
+    }
+
+    fn user() -> KeyPath<Transaction, String> {
+        KeyPath::new(|t: &Transaction| &t.user, |t, user| t.user = user)
+    }
+
+    fn amount() -> KeyPath<Transaction, u32> {
+        KeyPath::new(|t: &Transaction| &t.amount, |t, amount| t.amount = amount)
+    }
+
+    #[test]
+    fn test_sorted_by_keypath_is_stable() {
+        let sorted = sorted_by_keypath(vec![tx("bob", 1), tx("ann", 2), tx("bob", 0)], user());
+        assert_eq!(sorted, vec![tx("ann", 2), tx("bob", 1), tx("bob", 0)]);
+    }
+
+    #[test]
+    fn test_sort_by_keys_in_place() {
+        let mut txs = vec![tx("bob", 5), tx("ann", 9), tx("bob", 1), tx("ann", 3)];
+        sort_by_keys(&mut txs, (user(), amount()));
+        assert_eq!(txs, vec![tx("ann", 3), tx("ann", 9), tx("bob", 1), tx("bob", 5)]);
+        assert_eq!(sorted_by_keys(txs.clone(), (amount(),))[0], tx("bob", 1));
+    }
+
+    #[test]
+    fn test_sum_and_count_by() {
+        let txs = vec![tx("ann", 10), tx("bob", 25), tx("ann", 5)];
+        assert_eq!(sum_by(&txs, |t| t.amount), 40);
+        assert_eq!(sum_by(&txs, crate::keypath::get(amount())), 40);
+        assert_eq!(count_by(&txs, |t| t.user == "ann"), 2);
+    }
+
+    #[test]
+    fn test_average_by() {
+        let temperatures = [20.0, 22.0, 27.0];
+        assert_eq!(average_by(temperatures, |t| t), Some(23.0));
+        assert_eq!(average_by(Vec::<f64>::new(), |t| t), None);
+    }
+
+    #[test]
+    fn test_percentile_by() {
+        let latencies = [15.0, 20.0, 35.0, 40.0, 50.0];
+        assert_eq!(percentile_by(latencies, 0.0, |x| x), Some(15.0));
+        assert_eq!(percentile_by(latencies, 50.0, |x| x), Some(35.0));
+        assert_eq!(percentile_by(latencies, 90.0, |x| x), Some(46.0));
+        assert_eq!(percentile_by(latencies, 100.0, |x| x), Some(50.0));
+        assert_eq!(percentile_by(Vec::<f64>::new(), 50.0, |x| x), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_index_by_last_wins() {
+        let by_user = index_by(vec![tx("ann", 1), tx("bob", 2), tx("ann", 3)], |t| t.user.clone());
+        assert_eq!(by_user["ann"], tx("ann", 3));
+        assert_eq!(by_user.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_index_by_keypath_and_many() {
+        let by_amount = index_by_keypath(vec![tx("ann", 1), tx("bob", 2)], amount());
+        assert_eq!(by_amount[&2].user, "bob");
+        let all = index_by_many(vec![tx("ann", 1), tx("ann", 3)], |t| t.user.clone());
+        assert_eq!(all["ann"].len(), 2);
+    }
+}
@@ -0,0 +1,113 @@
+// Side effects that pass the value through unchanged, for logging/debugging
+// inside `pipe`/`compose` chains without breaking the type flow.
+
+/// Blanket extension trait giving every value `tap` and `tap_mut`.
+pub trait Tap: Sized {
+    /// Run `f` on a borrow of `self`, then return `self`.
+    fn tap(self, f: impl FnOnce(&Self)) -> Self {
+        f(&self);
+        self
+    }
+
+    /// Run `f` on a mutable borrow of `self`, then return `self`.
+    fn tap_mut(mut self, f: impl FnOnce(&mut Self)) -> Self {
+        f(&mut self);
+        self
+    }
+}
+
+impl<T> Tap for T {}
+
+/// Turn an observer into a pass-through stage: `(A) -> A`
+pub fn tap<A>(f: impl Fn(&A)) -> impl Fn(A) -> A {
+    move |a: A| {
+        f(&a);
+        a
+    }
+}
+
+// Throwing versions: wrap a throwing stage and observe its success or failure channel,
+// e.g. to count errors per stage. The result is passed on unchanged.
+
+/// Observe the successes of a throwing function.
+pub fn tap_ok<A, B, E, F>(f: F, observe: impl Fn(&B)) -> impl Fn(A) -> Result<B, E>
+where
+    F: Fn(A) -> Result<B, E>,
+{
+    move |a: A| f(a).inspect(&observe)
+}
+
+/// Observe the failures of a throwing function.
+pub fn tap_err<A, B, E, F>(f: F, observe: impl Fn(&E)) -> impl Fn(A) -> Result<B, E>
+where
+    F: Fn(A) -> Result<B, E>,
+{
+    move |a: A| f(a).inspect_err(&observe)
+}
+
+/// Observe every result of a throwing function, success or failure.
+pub fn inspect<A, B, E, F>(f: F, observe: impl Fn(&Result<B, E>)) -> impl Fn(A) -> Result<B, E>
+where
+    F: Fn(A) -> Result<B, E>,
+{
+    move |a: A| {
+        let result = f(a);
+        observe(&result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::chain2_throwing;
+    use crate::compose::compose3;
+    use crate::pipe::Pipe;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_tap_method() {
+        let seen = RefCell::new(Vec::new());
+        let result = 2
+            .pipe(|x| x * 10)
+            .tap(|x| seen.borrow_mut().push(*x))
+            .pipe(|x| x + 1);
+        assert_eq!(result, 21);
+        assert_eq!(*seen.borrow(), vec![20]);
+    }
+
+    #[test]
+    fn test_tap_mut_method() {
+        let v = vec![3, 1, 2].tap_mut(|v| v.sort());
+        assert_eq!(v, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_tap_in_compose() {
+        let log = RefCell::new(Vec::new());
+        let f = compose3(
+            |x: i32| x + 1,
+            tap(|x: &i32| log.borrow_mut().push(format!("doubled: {}", x))),
+            |x: i32| x * 2,
+        );
+        assert_eq!(f(5), 11);
+        assert_eq!(*log.borrow(), vec!["doubled: 10".to_string()]);
+    }
+
+    #[test]
+    fn test_tap_ok_and_tap_err_in_throwing_chain() {
+        let (parsed, failures, results) = (RefCell::new(0), RefCell::new(Vec::new()), RefCell::new(0));
+        let parse = tap_err(
+            tap_ok(|s: &str| s.parse::<i32>().map_err(|e| e.to_string()), |_| *parsed.borrow_mut() += 1),
+            |e: &String| failures.borrow_mut().push(e.clone()),
+        );
+        let f = chain2_throwing(parse, inspect(|n: i32| if n > 0 { Ok(n) } else { Err(format!("{n} <= 0")) }, |_| *results.borrow_mut() += 1));
+        let ok: Result<i32, String> = f("3");
+        assert_eq!(ok, Ok(3));
+        assert!(f("-1").is_err());
+        assert!(f("x").is_err());
+        assert_eq!(*parsed.borrow(), 2);
+        assert_eq!(*failures.borrow(), vec!["invalid digit found in string".to_string()]);
+        assert_eq!(*results.borrow(), 2);
+    }
+}
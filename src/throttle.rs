@@ -0,0 +1,155 @@
+use std::fmt;
+use std::sync::{Mutex, PoisonError};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::thread;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::time::Instant;
+use std::time::Duration;
+
+use crate::clock::Clock;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use crate::clock::StdClock;
+
+/// Error returned by `throttled` when a call comes too soon after the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Throttled {
+    /// How long the caller has to wait before the next call is accepted.
+    pub retry_after: Duration,
+}
+
+impl fmt::Display for Throttled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "call throttled, retry after {:?}", self.retry_after)
+    }
+}
+
+impl std::error::Error for Throttled {}
+
+/// Reject calls that start less than `min_interval` after the last accepted call.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn throttled<A, B, F>(min_interval: Duration, f: F) -> impl Fn(A) -> Result<B, Throttled>
+where
+    F: Fn(A) -> B,
+{
+    throttled_with_clock(min_interval, StdClock, f)
+}
+
+/// `throttled`, reading the time from `clock`.
+pub fn throttled_with_clock<A, B, F, C>(min_interval: Duration, clock: C, f: F) -> impl Fn(A) -> Result<B, Throttled>
+where
+    F: Fn(A) -> B,
+    C: Clock,
+{
+    let last_call: Mutex<Option<Duration>> = Mutex::new(None);
+    move |a: A| {
+        {
+            let mut last_call = last_call.lock().unwrap_or_else(PoisonError::into_inner);
+            let now = clock.now();
+            if let Some(last) = *last_call {
+                let elapsed = now.saturating_sub(last);
+                if elapsed < min_interval {
+                    return Err(Throttled { retry_after: min_interval - elapsed });
+                }
+            }
+            *last_call = Some(now);
+        }
+        Ok(f(a))
+    }
+}
+
+/// Delay calls so that consecutive calls start at least `min_interval` apart.
+/// Blocks the calling thread, so it is not available on `wasm32-unknown-unknown`.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn throttled_blocking<A, B, F>(min_interval: Duration, f: F) -> impl Fn(A) -> B
+where
+    F: Fn(A) -> B,
+{
+    let next_slot: Mutex<Option<Instant>> = Mutex::new(None);
+    move |a: A| {
+        let start = {
+            let mut next_slot = next_slot.lock().unwrap_or_else(PoisonError::into_inner);
+            let now = Instant::now();
+            let start = next_slot.map_or(now, |slot| slot.max(now));
+            *next_slot = Some(start + min_interval);
+            start
+        };
+        let wait = start.saturating_duration_since(Instant::now());
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+        f(a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::chain2_throwing;
+    use crate::clock::ManualClock;
+
+    #[test]
+    fn test_throttled_rejects_close_calls() {
+        let f = throttled(Duration::from_secs(60), |x: i32| x + 1);
+        assert_eq!(f(1), Ok(2));
+        let err = f(2).unwrap_err();
+        assert!(err.retry_after <= Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_throttled_zero_interval_never_rejects() {
+        let f = throttled(Duration::ZERO, |x: i32| x);
+        for i in 0..10 {
+            assert_eq!(f(i), Ok(i));
+        }
+    }
+
+    #[test]
+    fn test_throttled_blocking_spaces_calls() {
+        let interval = Duration::from_millis(20);
+        let f = throttled_blocking(interval, |_: ()| Instant::now());
+        let begin = Instant::now();
+        f(());
+        f(());
+        let third = f(());
+        assert!(third.duration_since(begin) >= interval * 2);
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum LookupError {
+        RateLimited,
+        NotFound,
+    }
+
+    impl From<Throttled> for LookupError {
+        fn from(_: Throttled) -> Self {
+            LookupError::RateLimited
+        }
+    }
+
+    #[test]
+    fn test_throttled_composes_with_throwing_chains() {
+        let lookup = throttled(Duration::from_secs(60), |id: u32| id * 2);
+        let pipeline = chain2_throwing(lookup, |n: u32| {
+            if n > 0 { Ok(n) } else { Err(LookupError::NotFound) }
+        });
+        assert_eq!(pipeline(4), Ok(8));
+        assert_eq!(pipeline(4), Err(LookupError::RateLimited));
+    }
+
+    #[test]
+    fn test_throttled_display() {
+        let err = Throttled { retry_after: Duration::from_millis(5) };
+        assert_eq!(err.to_string(), "call throttled, retry after 5ms");
+    }
+
+    #[test]
+    fn test_throttled_with_manual_clock() {
+        let clock = ManualClock::default();
+        let submit = throttled_with_clock(Duration::from_millis(500), clock.clone(), |form: &str| form.len());
+        assert_eq!(submit("name=a"), Ok(6));
+        clock.advance(Duration::from_millis(200));
+        assert_eq!(submit("name=ab"), Err(Throttled { retry_after: Duration::from_millis(300) }));
+        clock.advance(Duration::from_millis(300));
+        assert_eq!(submit("name=abc"), Ok(8));
+    }
+}
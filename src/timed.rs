@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use crate::clock::Clock;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use crate::clock::StdClock;
+
+/// Receives the wall-clock duration of every call made through `timed_with`.
+/// Any `Fn(&str, Duration)` closure is a sink.
+pub trait TimingSink {
+    fn record(&self, label: &str, elapsed: Duration);
+}
+
+impl<F> TimingSink for F
+where
+    F: Fn(&str, Duration),
+{
+    fn record(&self, label: &str, elapsed: Duration) {
+        self(label, elapsed)
+    }
+}
+
+/// Sink printing `label: elapsed` to stderr; the default for `timed`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StderrSink;
+
+impl TimingSink for StderrSink {
+    fn record(&self, label: &str, elapsed: Duration) {
+        eprintln!("{}: {:?}", label, elapsed);
+    }
+}
+
+/// Measure each call of `f` and print its duration to stderr under `label`.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn timed<A, B, F>(label: impl Into<String>, f: F) -> impl Fn(A) -> B
+where
+    F: Fn(A) -> B,
+{
+    timed_with(label, StderrSink, f)
+}
+
+/// Measure each call of `f` and report its duration to `sink` under `label`.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn timed_with<A, B, F, S>(label: impl Into<String>, sink: S, f: F) -> impl Fn(A) -> B
+where
+    F: Fn(A) -> B,
+    S: TimingSink,
+{
+    timed_with_clock(label, sink, StdClock, f)
+}
+
+/// `timed_with`, measuring with `clock`.
+pub fn timed_with_clock<A, B, F, S, C>(label: impl Into<String>, sink: S, clock: C, f: F) -> impl Fn(A) -> B
+where
+    F: Fn(A) -> B,
+    S: TimingSink,
+    C: Clock,
+{
+    let label = label.into();
+    move |a: A| {
+        let start = clock.now();
+        let b = f(a);
+        sink.record(&label, clock.now().saturating_sub(start));
+        b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compose::compose3;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_timed_with_reports_each_call() {
+        let reports = RefCell::new(Vec::new());
+        let sink = |label: &str, elapsed: Duration| reports.borrow_mut().push((label.to_string(), elapsed));
+        let f = timed_with("double", sink, |x: i32| x * 2);
+        assert_eq!(f(2), 4);
+        assert_eq!(f(5), 10);
+        let reports = reports.borrow();
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|(label, _)| label == "double"));
+    }
+
+    #[test]
+    fn test_timed_with_finds_slow_stage() {
+        let reports = RefCell::new(Vec::new());
+        let sink = |label: &str, elapsed: Duration| reports.borrow_mut().push((label.to_string(), elapsed));
+        let pipeline = compose3(
+            timed_with("fast", &sink, |x: u64| x + 1),
+            timed_with(
+                "slow",
+                &sink,
+                |x: u64| {
+                    std::thread::sleep(Duration::from_millis(10));
+                    x
+                },
+            ),
+            timed_with("parse", &sink, |s: &str| s.len() as u64),
+        );
+        assert_eq!(pipeline("abc"), 4);
+        let reports = reports.borrow();
+        let labels: Vec<&str> = reports.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, vec!["parse", "slow", "fast"]);
+        let slowest = reports.iter().max_by_key(|(_, elapsed)| *elapsed).unwrap();
+        assert_eq!(slowest.0, "slow");
+    }
+
+    #[test]
+    fn test_timed_default_sink() {
+        let f = timed("identity", |x: i32| x);
+        assert_eq!(f(7), 7);
+    }
+
+    #[test]
+    fn test_timed_with_clock() {
+        let clock = crate::clock::ManualClock::default();
+        let reports = RefCell::new(Vec::new());
+        let sink = |_: &str, elapsed: Duration| reports.borrow_mut().push(elapsed);
+        let ticking = clock.clone();
+        let f = timed_with_clock("check", sink, clock, move |x: i32| {
+            ticking.advance(Duration::from_millis(15));
+            x
+        });
+        assert_eq!(f(3), 3);
+        assert_eq!(*reports.borrow(), vec![Duration::from_millis(15)]);
+    }
+}
@@ -0,0 +1,140 @@
+// Pipelines that can record every intermediate value, so a failing run can be inspected
+// stage by stage. Values are recorded with their `Debug` output; recording is on by
+// default in debug builds only.
+
+use core::cell::RefCell;
+use core::fmt;
+
+use crate::prelude::*;
+use crate::named::{Named, named};
+use crate::result::Contextual;
+
+/// What one stage produced: the `Debug` output of its value or of its error.
+/// The pipeline input is recorded as a stage named `"input"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub stage: &'static str,
+    pub value: Result<String, String>,
+}
+
+type Run<A, B, E> = Box<dyn Fn(A, &mut Option<Vec<TraceEntry>>) -> Result<B, Contextual<E>>>;
+
+/// Like `NamedPipeline`, but every stage output and error must be `Debug` so runs can be
+/// traced.
+pub struct Traced<A, B, E> {
+    f: Run<A, B, E>,
+    enabled: bool,
+    last_trace: RefCell<Vec<TraceEntry>>,
+}
+
+impl<A: fmt::Debug + 'static, E: 'static> Traced<A, A, E> {
+    pub fn new() -> Self {
+        let f: Run<A, A, E> = Box::new(|a, trace| {
+            if let Some(trace) = trace {
+                trace.push(TraceEntry { stage: "input", value: Ok(format!("{a:?}")) });
+            }
+            Ok(a)
+        });
+        Self { f, enabled: cfg!(debug_assertions), last_trace: RefCell::new(Vec::new()) }
+    }
+}
+
+impl<A: fmt::Debug + 'static, E: 'static> Default for Traced<A, A, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: 'static, B: 'static, E: 'static> Traced<A, B, E> {
+    /// Append a fallible stage; its error converts `Into` the pipeline's `E`.
+    pub fn then<C, E2, G>(self, stage: Named<G>) -> Traced<A, C, E>
+    where
+        G: Fn(B) -> Result<C, E2> + 'static,
+        C: fmt::Debug,
+        E2: fmt::Debug + Into<E>,
+    {
+        let (f, name, g) = (self.f, stage.name(), stage.into_fn());
+        let run: Run<A, C, E> = Box::new(move |a, trace| {
+            let result = g(f(a, trace)?);
+            if let Some(trace) = trace {
+                let value = result.as_ref().map(|c| format!("{c:?}")).map_err(|e| format!("{e:?}"));
+                trace.push(TraceEntry { stage: name, value });
+            }
+            result.map_err(|e| Contextual { context: name.to_string(), error: e.into() })
+        });
+        Traced { f: run, enabled: self.enabled, last_trace: self.last_trace }
+    }
+
+    /// `then(named(name, f))`.
+    pub fn stage<C, E2, G>(self, name: &'static str, f: G) -> Traced<A, C, E>
+    where
+        G: Fn(B) -> Result<C, E2> + 'static,
+        C: fmt::Debug,
+        E2: fmt::Debug + Into<E>,
+    {
+        self.then(named(name, f))
+    }
+
+    /// Turn recording in `run` on or off; `run_traced` always records.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Run the pipeline; if recording is enabled, the trace is kept for `last_trace`.
+    pub fn run(&self, a: A) -> Result<B, Contextual<E>> {
+        if !self.enabled {
+            return (self.f)(a, &mut None);
+        }
+        let (result, trace) = self.run_traced(a);
+        *self.last_trace.borrow_mut() = trace;
+        result
+    }
+
+    /// Run the pipeline and return the trace with the result.
+    pub fn run_traced(&self, a: A) -> (Result<B, Contextual<E>>, Vec<TraceEntry>) {
+        let mut trace = Some(Vec::new());
+        let result = (self.f)(a, &mut trace);
+        (result, trace.unwrap_or_default())
+    }
+
+    /// The trace of the most recent recorded `run`.
+    pub fn last_trace(&self) -> Vec<TraceEntry> {
+        self.last_trace.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipeline() -> Traced<&'static str, u32, String> {
+        Traced::new()
+            .stage("parse", |s: &str| s.trim().parse::<u32>().map_err(|e| e.to_string()))
+            .stage("fee", |n: u32| n.checked_sub(5).ok_or_else(|| format!("{n} below fee")))
+    }
+
+    #[test]
+    fn test_run_traced_records_each_stage() {
+        let (result, trace) = pipeline().run_traced(" 3 ");
+        assert_eq!(result.unwrap_err().context, "fee");
+        assert_eq!(
+            trace,
+            vec![
+                TraceEntry { stage: "input", value: Ok(r#"" 3 ""#.to_string()) },
+                TraceEntry { stage: "parse", value: Ok("3".to_string()) },
+                TraceEntry { stage: "fee", value: Err(r#""3 below fee""#.to_string()) },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_keeps_last_trace_only_when_enabled() {
+        let p = pipeline().enabled(true);
+        assert_eq!(p.run("10"), Ok(5));
+        assert_eq!(p.last_trace().len(), 3);
+        let quiet = pipeline().enabled(false);
+        assert_eq!(quiet.run("10"), Ok(5));
+        assert!(quiet.last_trace().is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+use core::convert::Infallible;
+
+use crate::prelude::*;
+use crate::keypath::KeyPath;
+
+/// Puts a field back to the value it had before a step ran.
+type Undo<'a, Root> = Box<dyn FnOnce(&mut Root) + 'a>;
+
+/// Runs one update. Undo records are only built when `undos` is given (`apply_mut`);
+/// `apply` works on a copy and passes `None`, so it pays for neither the extra clone
+/// of the old value nor the boxed undo closure.
+type Step<'a, Root, E> = Box<dyn Fn(&mut Root, Option<&mut Vec<Undo<'a, Root>>>) -> Result<(), E> + 'a>;
+
+/// A batch of keypath updates applied in one pass, all or nothing: if a fallible
+/// update fails, none of the batch's changes are visible.
+///
+/// Chaining N `over`/`set_value` functions moves the root through N calls; a
+/// transaction instead clones it at most once (`apply`) or not at all (`apply_mut`).
+pub struct Transaction<'a, Root, E = Infallible> {
+    steps: Vec<Step<'a, Root, E>>,
+}
+
+impl<'a, Root: 'a, E> Transaction<'a, Root, E> {
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Queue setting a field to `value`.
+    pub fn set<Value>(self, keypath: KeyPath<Root, Value>, value: Value) -> Self
+    where
+        Value: Clone + 'a,
+    {
+        self.try_over(keypath, move |_| Ok(value.clone()))
+    }
+
+    /// Queue an update of a field.
+    pub fn over<Value, F>(self, keypath: KeyPath<Root, Value>, update: F) -> Self
+    where
+        Value: Clone + 'a,
+        F: Fn(Value) -> Value + 'a,
+    {
+        self.try_over(keypath, move |v| Ok(update(v)))
+    }
+
+    /// Queue a fallible update of a field; its error aborts the whole transaction.
+    pub fn try_over<Value, F>(mut self, keypath: KeyPath<Root, Value>, update: F) -> Self
+    where
+        Value: Clone + 'a,
+        F: Fn(Value) -> Result<Value, E> + 'a,
+    {
+        self.steps.push(Box::new(move |root: &mut Root, undos: Option<&mut Vec<Undo<'a, Root>>>| {
+            let old = (keypath.get)(root).clone();
+            match undos {
+                None => (keypath.set)(root, update(old)?),
+                Some(undos) => {
+                    (keypath.set)(root, update(old.clone())?);
+                    undos.push(Box::new(move |root: &mut Root| (keypath.set)(root, old)));
+                }
+            }
+            Ok(())
+        }));
+        self
+    }
+
+    /// Apply every update to a single clone of `root`, which itself is never modified.
+    pub fn apply(&self, root: &Root) -> Result<Root, E>
+    where
+        Root: Clone,
+    {
+        let mut updated = root.clone();
+        for step in &self.steps {
+            // Working on a copy, so there is nothing to undo on failure.
+            step(&mut updated, None)?;
+        }
+        Ok(updated)
+    }
+
+    /// Apply every update in place. On error, the fields changed so far are restored
+    /// (in reverse order) before the error is returned, so no clone of `root` is needed.
+    pub fn apply_mut(&self, root: &mut Root) -> Result<(), E> {
+        let mut undos = Vec::with_capacity(self.steps.len());
+        for step in &self.steps {
+            if let Err(e) = step(root, Some(&mut undos)) {
+                undos.into_iter().rev().for_each(|undo| undo(root));
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, Root: 'a, E> Default for Transaction<'a, Root, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Payment {
+        amount: u64,
+        currency: String,
+        reference: String,
+    }
+
+    fn amount() -> KeyPath<Payment, u64> {
+        KeyPath::new(|p: &Payment| &p.amount, |p, v| p.amount = v)
+    }
+
+    fn currency() -> KeyPath<Payment, String> {
+        KeyPath::new(|p: &Payment| &p.currency, |p, v| p.currency = v)
+    }
+
+    fn reference() -> KeyPath<Payment, String> {
+        KeyPath::new(|p: &Payment| &p.reference, |p, v| p.reference = v)
+    }
+
+    fn payment() -> Payment {
+        Payment { amount: 100, currency: "eur".into(), reference: "inv-1".into() }
+    }
+
+    fn normalize_currency(code: String) -> Result<String, String> {
+        if code.len() == 3 { Ok(code.to_uppercase()) } else { Err(format!("invalid currency {code}")) }
+    }
+
+    #[test]
+    fn test_apply_clones_once_and_keeps_original() {
+        let tx: Transaction<Payment> = Transaction::new().over(amount(), |a| a * 2).set(reference(), "inv-2".into());
+        let original = payment();
+        let updated = tx.apply(&original).unwrap();
+        assert_eq!(updated, Payment { amount: 200, currency: "eur".into(), reference: "inv-2".into() });
+        assert_eq!(original, payment());
+    }
+
+    #[test]
+    fn test_apply_mut_all_or_nothing() {
+        let tx = Transaction::new()
+            .over(amount(), |a| a + 1)
+            .try_over(currency(), normalize_currency)
+            .set(reference(), "changed".into())
+            .try_over(amount(), |a| if a > 1000 { Err("too large".to_string()) } else { Ok(a) });
+        assert_eq!(tx.len(), 4);
+
+        let mut p = payment();
+        assert_eq!(tx.apply_mut(&mut p), Ok(()));
+        assert_eq!(p, Payment { amount: 101, currency: "EUR".into(), reference: "changed".into() });
+
+        let mut big = Payment { amount: 5000, ..payment() };
+        assert_eq!(tx.apply_mut(&mut big), Err("too large".to_string()));
+        assert_eq!(big, Payment { amount: 5000, ..payment() }, "every field is rolled back");
+    }
+
+    #[test]
+    fn test_same_field_updated_twice_rolls_back_to_original() {
+        let tx = Transaction::new()
+            .over(amount(), |a| a + 1)
+            .over(amount(), |a| a * 10)
+            .try_over(currency(), normalize_currency);
+        let mut p = Payment { currency: "euro".into(), ..payment() };
+        assert!(tx.apply_mut(&mut p).is_err());
+        assert_eq!(p.amount, 100);
+        assert!(Transaction::<Payment>::default().is_empty());
+    }
+
+    #[test]
+    fn test_apply_clones_each_old_value_once() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct Counted(Rc<Cell<u32>>, u64);
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                self.0.set(self.0.get() + 1);
+                Counted(Rc::clone(&self.0), self.1)
+            }
+        }
+        #[derive(Clone)]
+        struct Account {
+            balance: Counted,
+        }
+        let balance = KeyPath::new(|a: &Account| &a.balance, |a, v| a.balance = v);
+        let clones = Rc::new(Cell::new(0));
+        let mut account = Account { balance: Counted(Rc::clone(&clones), 10) };
+        let tx: Transaction<Account> = Transaction::new().over(balance, |c: Counted| Counted(c.0, c.1 + 5));
+        let updated = tx.apply(&account).unwrap();
+        // One clone of the root, one of the old value, no undo copy.
+        assert_eq!((updated.balance.1, clones.get()), (15, 2));
+        clones.set(0);
+        tx.apply_mut(&mut account).unwrap();
+        assert_eq!((account.balance.1, clones.get()), (15, 2));
+    }
+}
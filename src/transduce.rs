@@ -0,0 +1,214 @@
+// Transducers: transformations such as `mapping(f)` or `filtering(p)` as values,
+// independent of any collection. They compose with `then` and run in a single pass
+// through `into_vec`, `into_iter` or `transduce`, with no intermediate `Vec`s.
+
+use alloc::collections::VecDeque;
+
+use crate::prelude::*;
+
+/// A step of a transducer pipeline: turns each input into zero or more outputs.
+pub trait Transducer<A> {
+    type Output;
+
+    /// Feed one input, passing every output to `emit`. Returns `false` once the
+    /// pipeline wants no more input (a `taking` stage is done, or `emit` said stop).
+    fn step<E>(&mut self, a: A, emit: &mut E) -> bool
+    where
+        E: FnMut(Self::Output) -> bool;
+
+    /// Run `self`, then `next` on its outputs.
+    fn then<T>(self, next: T) -> Then<Self, T>
+    where
+        Self: Sized,
+        T: Transducer<Self::Output>,
+    {
+        Then { first: self, second: next }
+    }
+}
+
+pub struct Mapping<F>(F);
+
+/// Transform every element.
+pub fn mapping<F>(f: F) -> Mapping<F> {
+    Mapping(f)
+}
+
+impl<A, B, F> Transducer<A> for Mapping<F>
+where
+    F: FnMut(A) -> B,
+{
+    type Output = B;
+
+    fn step<E>(&mut self, a: A, emit: &mut E) -> bool
+    where
+        E: FnMut(B) -> bool,
+    {
+        emit((self.0)(a))
+    }
+}
+
+pub struct Filtering<P>(P);
+
+/// Keep the elements satisfying `predicate`.
+pub fn filtering<P>(predicate: P) -> Filtering<P> {
+    Filtering(predicate)
+}
+
+impl<A, P> Transducer<A> for Filtering<P>
+where
+    P: FnMut(&A) -> bool,
+{
+    type Output = A;
+
+    fn step<E>(&mut self, a: A, emit: &mut E) -> bool
+    where
+        E: FnMut(A) -> bool,
+    {
+        if (self.0)(&a) { emit(a) } else { true }
+    }
+}
+
+pub struct Taking {
+    remaining: usize,
+}
+
+/// Stop after `n` elements; later input is not even read.
+pub fn taking(n: usize) -> Taking {
+    Taking { remaining: n }
+}
+
+impl<A> Transducer<A> for Taking {
+    type Output = A;
+
+    fn step<E>(&mut self, a: A, emit: &mut E) -> bool
+    where
+        E: FnMut(A) -> bool,
+    {
+        if self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        emit(a) && self.remaining > 0
+    }
+}
+
+pub struct Then<T1, T2> {
+    first: T1,
+    second: T2,
+}
+
+impl<A, T1, T2> Transducer<A> for Then<T1, T2>
+where
+    T1: Transducer<A>,
+    T2: Transducer<T1::Output>,
+{
+    type Output = T2::Output;
+
+    fn step<E>(&mut self, a: A, emit: &mut E) -> bool
+    where
+        E: FnMut(T2::Output) -> bool,
+    {
+        let second = &mut self.second;
+        let mut keep_going = true;
+        let first_continues = self.first.step(a, &mut |b| {
+            keep_going = second.step(b, emit);
+            keep_going
+        });
+        first_continues && keep_going
+    }
+}
+
+/// Fold the transformed elements into `init`.
+pub fn transduce<I, T, B, F>(mut xf: T, items: I, init: B, mut f: F) -> B
+where
+    I: IntoIterator,
+    T: Transducer<I::Item>,
+    F: FnMut(B, T::Output) -> B,
+{
+    let mut acc = Some(init);
+    for item in items {
+        let keep_going = xf.step(item, &mut |out| {
+            acc = acc.take().map(|acc| f(acc, out));
+            true
+        });
+        if !keep_going {
+            break;
+        }
+    }
+    acc.expect("the accumulator is always put back")
+}
+
+/// Collect the transformed elements.
+pub fn into_vec<I, T>(xf: T, items: I) -> Vec<T::Output>
+where
+    I: IntoIterator,
+    T: Transducer<I::Item>,
+{
+    transduce(xf, items, Vec::new(), |mut v, out| {
+        v.push(out);
+        v
+    })
+}
+
+/// The transformed elements, lazily: input is read only as far as needed.
+pub fn into_iter<I, T>(mut xf: T, items: I) -> impl Iterator<Item = T::Output>
+where
+    I: IntoIterator,
+    T: Transducer<I::Item>,
+{
+    let mut items = items.into_iter();
+    let mut buffer = VecDeque::new();
+    let mut done = false;
+    core::iter::from_fn(move || {
+        while buffer.is_empty() && !done {
+            match items.next() {
+                Some(item) => {
+                    done = !xf.step(item, &mut |out| {
+                        buffer.push_back(out);
+                        true
+                    })
+                }
+                None => done = true,
+            }
+        }
+        buffer.pop_front()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_vec() {
+        let xf = mapping(|n: i32| n * 3).then(filtering(|n: &i32| n % 2 == 0)).then(taking(2));
+        assert_eq!(into_vec(xf, 1..=10), vec![6, 12]);
+    }
+
+    #[test]
+    fn test_taking_stops_reading_input() {
+        let mut read = 0;
+        let inputs = std::iter::repeat_with(|| {
+            read += 1;
+            read
+        });
+        assert_eq!(into_vec(taking(3), inputs), vec![1, 2, 3]);
+        assert_eq!(read, 3);
+    }
+
+    #[test]
+    fn test_into_iter_is_lazy_over_infinite_input() {
+        let xf = filtering(|n: &u64| n.is_multiple_of(7)).then(mapping(|n: u64| n.to_string()));
+        let first: Vec<String> = into_iter(xf, 1..).take(2).collect();
+        assert_eq!(first, vec!["7", "14"]);
+    }
+
+    #[test]
+    fn test_transduce_fold_and_reuse() {
+        let pipeline = || mapping(|s: &str| s.len()).then(filtering(|n: &usize| *n > 1));
+        let words = ["a", "bb", "ccc"];
+        assert_eq!(transduce(pipeline(), words, 0, |sum, n| sum + n), 5);
+        assert_eq!(into_vec(pipeline(), vec!["dd"; 2]), vec![2, 2]);
+        assert_eq!(into_vec(taking(0), words), Vec::<&str>::new());
+    }
+}
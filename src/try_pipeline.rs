@@ -0,0 +1,173 @@
+use core::marker::PhantomData;
+
+use crate::prelude::*;
+
+/// A pipeline of fallible steps `(A) -> Result<B, E>`, built one step at a time with
+/// `step` and run with `run(a)?` in ordinary application code. As with
+/// `chainN_throwing`, each step may have its own error type as long as it converts
+/// `Into` the pipeline's `E`. Like `Func`, the default `F` is boxed so a
+/// `TryPipeline<A, B, E>` can be stored in a struct field.
+pub struct TryPipeline<A, B, E, F = Box<dyn Fn(A) -> Result<B, E>>> {
+    f: F,
+    _marker: PhantomData<fn(A) -> Result<B, E>>,
+}
+
+/// The boxed `TryPipeline`, e.g. for a registry of validators keyed by payment method.
+pub type BoxTryFn<A, B, E> = TryPipeline<A, B, E>;
+
+impl<A, E> TryPipeline<A, A, E, fn(A) -> Result<A, E>> {
+    /// An empty pipeline, returning its input.
+    pub fn new() -> Self {
+        Self::from_fn(Ok)
+    }
+}
+
+impl<A, E> Default for TryPipeline<A, A, E, fn(A) -> Result<A, E>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, B, E, F> TryPipeline<A, B, E, F>
+where
+    F: Fn(A) -> Result<B, E>,
+{
+    /// A pipeline starting with an existing throwing function.
+    pub fn from_fn(f: F) -> Self {
+        Self { f, _marker: PhantomData }
+    }
+
+    /// Run the steps in order, stopping at the first error.
+    pub fn run(&self, a: A) -> Result<B, E> {
+        (self.f)(a)
+    }
+
+    /// Append a fallible step.
+    pub fn step<C, E2, G>(self, g: G) -> TryPipeline<A, C, E, impl Fn(A) -> Result<C, E>>
+    where
+        G: Fn(B) -> Result<C, E2>,
+        E2: Into<E>,
+    {
+        let f = self.f;
+        TryPipeline::from_fn(move |a: A| g(f(a)?).map_err(Into::into))
+    }
+
+    /// Append a step that cannot fail.
+    pub fn map<C, G>(self, g: G) -> TryPipeline<A, C, E, impl Fn(A) -> Result<C, E>>
+    where
+        G: Fn(B) -> C,
+    {
+        let f = self.f;
+        TryPipeline::from_fn(move |a: A| f(a).map(&g))
+    }
+
+    // `Func` names for the same operations, so `BoxFn` and `BoxTryFn` values are used
+    // alike: `call`, `then` for a plain step, `and_then` for a fallible one.
+
+    /// Same as [`TryPipeline::run`].
+    pub fn call(&self, a: A) -> Result<B, E> {
+        self.run(a)
+    }
+
+    /// Same as [`TryPipeline::map`].
+    pub fn then<C, G>(self, g: G) -> TryPipeline<A, C, E, impl Fn(A) -> Result<C, E>>
+    where
+        G: Fn(B) -> C,
+    {
+        self.map(g)
+    }
+
+    /// Same as [`TryPipeline::step`].
+    pub fn and_then<C, E2, G>(self, g: G) -> TryPipeline<A, C, E, impl Fn(A) -> Result<C, E>>
+    where
+        G: Fn(B) -> Result<C, E2>,
+        E2: Into<E>,
+    {
+        self.step(g)
+    }
+
+    /// Back to a plain throwing function, e.g. for `chainN_throwing` or `traverse`.
+    pub fn into_fn(self) -> F {
+        self.f
+    }
+
+    /// Erase the closure type so differently-built pipelines share one type.
+    pub fn boxed(self) -> TryPipeline<A, B, E>
+    where
+        F: 'static,
+    {
+        TryPipeline::from_fn(Box::new(self.f) as Box<dyn Fn(A) -> Result<B, E>>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum ImportError {
+        Parse(String),
+        Range(i64),
+    }
+
+    impl From<std::num::ParseIntError> for ImportError {
+        fn from(e: std::num::ParseIntError) -> Self {
+            ImportError::Parse(e.to_string())
+        }
+    }
+
+    fn in_range(n: i64) -> Result<i64, ImportError> {
+        if (0..=100).contains(&n) { Ok(n) } else { Err(ImportError::Range(n)) }
+    }
+
+    fn import(rows: &[&str]) -> Result<i64, ImportError> {
+        let score: TryPipeline<&str, i64, ImportError, _> = TryPipeline::new().step(|s: &str| s.parse::<i64>()).step(in_range).map(|n| n * 2);
+        let mut total = 0;
+        for row in rows {
+            total += score.run(row)?;
+        }
+        Ok(total)
+    }
+
+    #[test]
+    fn test_run_with_question_mark() {
+        assert_eq!(import(&["1", "2"]), Ok(6));
+        assert_eq!(import(&["1", "200", "x"]), Err(ImportError::Range(200)));
+        assert!(matches!(import(&["x"]), Err(ImportError::Parse(_))));
+    }
+
+    #[test]
+    fn test_boxed_in_struct_field() {
+        struct Column {
+            parse: TryPipeline<String, i64, ImportError>,
+        }
+        let column = Column { parse: TryPipeline::new().step(|s: String| s.parse::<i64>()).step(in_range).boxed() };
+        assert_eq!(column.parse.run("42".to_string()), Ok(42));
+        assert_eq!(crate::result::traverse(["1", "101"].map(String::from), column.parse.into_fn()), Err(ImportError::Range(101)));
+    }
+
+    #[test]
+    fn test_box_try_fn_registry() {
+        fn long_enough(s: &str) -> Result<&str, String> {
+            if s.len() >= 15 { Ok(s) } else { Err(format!("{s}: too short")) }
+        }
+        fn digits(s: &str) -> Result<&str, String> {
+            if s.chars().all(|c| c.is_ascii_digit()) { Ok(s) } else { Err(format!("{s}: not a number")) }
+        }
+        let validators: Vec<(&str, BoxTryFn<&str, &str, String>)> =
+            vec![("iban", TryPipeline::new().step(long_enough).boxed()), ("card", TryPipeline::new().step(digits).boxed())];
+        let errors: Vec<String> = validators.iter().filter_map(|(_, v)| v.run("NL91ABNA").err()).collect();
+        assert_eq!(errors, vec!["NL91ABNA: too short".to_string(), "NL91ABNA: not a number".to_string()]);
+    }
+
+    #[test]
+    fn test_box_fn_and_box_try_fn_share_method_names() {
+        use crate::func::{BoxFn, Func};
+        let plain: BoxFn<&str, usize> = Func::new(|s: &str| s.trim()).then(str::len).boxed();
+        let checked: BoxTryFn<&str, i64, ImportError> =
+            TryPipeline::new().and_then(|s: &str| s.parse::<i64>()).and_then(in_range).then(|n| n + 1).boxed();
+        assert_eq!(plain.call(" ab "), 2);
+        assert_eq!(checked.call("41"), Ok(42));
+        assert_eq!(checked.call("500"), Err(ImportError::Range(500)));
+    }
+}
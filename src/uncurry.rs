@@ -0,0 +1,239 @@
+use alloc::sync::Arc;
+
+#[cfg(not(overture_nightly))]
+use crate::curry::{Curried2, Curried3};
+
+// Inverse of `curry`: turn a chain of single-argument functions
+// `(A1) -> (A2) -> ... -> R` back into a flat multi-argument function.
+
+/// One step of a curried function: anything that can be applied to a single argument.
+/// Covers plain closures as well as the `Arc<dyn Fn>` and `Curried*` values returned by `curry`.
+pub trait CurriedStep<A> {
+    type Output;
+    fn apply(&self, a: A) -> Self::Output;
+}
+
+impl<A, R, F> CurriedStep<A> for F
+where
+    F: Fn(A) -> R,
+{
+    type Output = R;
+    fn apply(&self, a: A) -> R {
+        self(a)
+    }
+}
+
+impl<A, R> CurriedStep<A> for Arc<dyn Fn(A) -> R + Send + Sync> {
+    type Output = R;
+    fn apply(&self, a: A) -> R {
+        self(a)
+    }
+}
+
+// With `--cfg overture_nightly` the curried structs are `Fn` and use the blanket impl.
+#[cfg(not(overture_nightly))]
+impl<F, A1, A2, R> CurriedStep<A2> for Curried2<F, (A1,)>
+where
+    F: Fn(A1, A2) -> R,
+    A1: Clone,
+{
+    type Output = R;
+    fn apply(&self, a2: A2) -> R {
+        self.call(a2)
+    }
+}
+
+#[cfg(not(overture_nightly))]
+impl<F, A1, A2> CurriedStep<A2> for Curried3<F, (A1,)>
+where
+    F: Clone,
+    A1: Clone,
+{
+    type Output = Curried3<F, (A1, A2)>;
+    fn apply(&self, a2: A2) -> Self::Output {
+        self.call(a2)
+    }
+}
+
+#[cfg(not(overture_nightly))]
+impl<F, A1, A2, A3, R> CurriedStep<A3> for Curried3<F, (A1, A2)>
+where
+    F: Fn(A1, A2, A3) -> R,
+    A1: Clone,
+    A2: Clone,
+{
+    type Output = R;
+    fn apply(&self, a3: A3) -> R {
+        self.call(a3)
+    }
+}
+
+pub fn uncurry2<A1, A2, R, F, G1>(function: F) -> impl Fn(A1, A2) -> R
+where
+    F: CurriedStep<A1, Output = G1>,
+    G1: CurriedStep<A2, Output = R>,
+{
+    move |a1, a2| function.apply(a1).apply(a2)
+}
+
+pub fn uncurry3<A1, A2, A3, R, F, G1, G2>(function: F) -> impl Fn(A1, A2, A3) -> R
+where
+    F: CurriedStep<A1, Output = G1>,
+    G1: CurriedStep<A2, Output = G2>,
+    G2: CurriedStep<A3, Output = R>,
+{
+    move |a1, a2, a3| function.apply(a1).apply(a2).apply(a3)
+}
+
+pub fn uncurry4<A1, A2, A3, A4, R, F, G1, G2, G3>(function: F) -> impl Fn(A1, A2, A3, A4) -> R
+where
+    F: CurriedStep<A1, Output = G1>,
+    G1: CurriedStep<A2, Output = G2>,
+    G2: CurriedStep<A3, Output = G3>,
+    G3: CurriedStep<A4, Output = R>,
+{
+    move |a1, a2, a3, a4| function.apply(a1).apply(a2).apply(a3).apply(a4)
+}
+
+pub fn uncurry5<A1, A2, A3, A4, A5, R, F, G1, G2, G3, G4>(function: F) -> impl Fn(A1, A2, A3, A4, A5) -> R
+where
+    F: CurriedStep<A1, Output = G1>,
+    G1: CurriedStep<A2, Output = G2>,
+    G2: CurriedStep<A3, Output = G3>,
+    G3: CurriedStep<A4, Output = G4>,
+    G4: CurriedStep<A5, Output = R>,
+{
+    move |a1, a2, a3, a4, a5| function.apply(a1).apply(a2).apply(a3).apply(a4).apply(a5)
+}
+
+pub fn uncurry6<A1, A2, A3, A4, A5, A6, R, F, G1, G2, G3, G4, G5>(function: F) -> impl Fn(A1, A2, A3, A4, A5, A6) -> R
+where
+    F: CurriedStep<A1, Output = G1>,
+    G1: CurriedStep<A2, Output = G2>,
+    G2: CurriedStep<A3, Output = G3>,
+    G3: CurriedStep<A4, Output = G4>,
+    G4: CurriedStep<A5, Output = G5>,
+    G5: CurriedStep<A6, Output = R>,
+{
+    move |a1, a2, a3, a4, a5, a6| function.apply(a1).apply(a2).apply(a3).apply(a4).apply(a5).apply(a6)
+}
+
+// ---------------------------------------------------
+// Throwing versions (Swift `throws` → Rust `Result`)
+// ---------------------------------------------------
+
+pub fn uncurry2_throwing<A1, A2, R, E, F, G1>(function: F) -> impl Fn(A1, A2) -> Result<R, E>
+where
+    F: CurriedStep<A1, Output = G1>,
+    G1: CurriedStep<A2, Output = Result<R, E>>,
+{
+    move |a1, a2| function.apply(a1).apply(a2)
+}
+
+pub fn uncurry3_throwing<A1, A2, A3, R, E, F, G1, G2>(function: F) -> impl Fn(A1, A2, A3) -> Result<R, E>
+where
+    F: CurriedStep<A1, Output = G1>,
+    G1: CurriedStep<A2, Output = G2>,
+    G2: CurriedStep<A3, Output = Result<R, E>>,
+{
+    move |a1, a2, a3| function.apply(a1).apply(a2).apply(a3)
+}
+
+pub fn uncurry4_throwing<A1, A2, A3, A4, R, E, F, G1, G2, G3>(function: F) -> impl Fn(A1, A2, A3, A4) -> Result<R, E>
+where
+    F: CurriedStep<A1, Output = G1>,
+    G1: CurriedStep<A2, Output = G2>,
+    G2: CurriedStep<A3, Output = G3>,
+    G3: CurriedStep<A4, Output = Result<R, E>>,
+{
+    move |a1, a2, a3, a4| function.apply(a1).apply(a2).apply(a3).apply(a4)
+}
+
+pub fn uncurry5_throwing<A1, A2, A3, A4, A5, R, E, F, G1, G2, G3, G4>(function: F) -> impl Fn(A1, A2, A3, A4, A5) -> Result<R, E>
+where
+    F: CurriedStep<A1, Output = G1>,
+    G1: CurriedStep<A2, Output = G2>,
+    G2: CurriedStep<A3, Output = G3>,
+    G3: CurriedStep<A4, Output = G4>,
+    G4: CurriedStep<A5, Output = Result<R, E>>,
+{
+    move |a1, a2, a3, a4, a5| function.apply(a1).apply(a2).apply(a3).apply(a4).apply(a5)
+}
+
+pub fn uncurry6_throwing<A1, A2, A3, A4, A5, A6, R, E, F, G1, G2, G3, G4, G5>(function: F) -> impl Fn(A1, A2, A3, A4, A5, A6) -> Result<R, E>
+where
+    F: CurriedStep<A1, Output = G1>,
+    G1: CurriedStep<A2, Output = G2>,
+    G2: CurriedStep<A3, Output = G3>,
+    G3: CurriedStep<A4, Output = G4>,
+    G4: CurriedStep<A5, Output = G5>,
+    G5: CurriedStep<A6, Output = Result<R, E>>,
+{
+    move |a1, a2, a3, a4, a5, a6| function.apply(a1).apply(a2).apply(a3).apply(a4).apply(a5).apply(a6)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curry::{curry2, curry2_throwing, curry3, curry3_unboxed};
+
+    #[test]
+    fn test_uncurry2_roundtrip() {
+        let add = |a: i32, b: i32| a + b;
+        let flat = uncurry2(curry2(add));
+        assert_eq!(flat(2, 3), 5);
+    }
+
+    #[test]
+    fn test_uncurry3_roundtrip() {
+        let multiply_add = |a: i32, b: i32, c: i32| a * b + c;
+        let flat = uncurry3(curry3(multiply_add));
+        assert_eq!(flat(2, 3, 4), 10);
+    }
+
+    #[test]
+    fn test_uncurry3_unboxed_roundtrip() {
+        let multiply_add = |a: i32, b: i32, c: i32| a * b + c;
+        let flat = uncurry3(curry3_unboxed(multiply_add));
+        assert_eq!(flat(2, 3, 4), 10);
+    }
+
+    #[test]
+    fn test_uncurry4_closures() {
+        let curried = |a: i32| move |b: i32| move |c: i32| move |d: i32| a * 1000 + b * 100 + c * 10 + d;
+        let flat = uncurry4(curried);
+        assert_eq!(flat(1, 2, 3, 4), 1234);
+    }
+
+    #[test]
+    fn test_uncurry6_different_types() {
+        let curried = |a: &'static str| {
+            move |b: i32| move |c: bool| move |d: char| move |e: u8| move |f: f64| {
+                format!("{}{}{}{}{}{}", a, b, c, d, e, f)
+            }
+        };
+        let flat = uncurry6(curried);
+        assert_eq!(flat("x", 1, true, 'c', 2, 0.5), "x1truec20.5");
+    }
+
+    #[test]
+    fn test_uncurry2_throwing() {
+        let safe_divide = |a: f64, b: f64| {
+            if b == 0.0 {
+                Err("Division by zero".to_string())
+            } else {
+                Ok(a / b)
+            }
+        };
+        let flat = uncurry2_throwing(curry2_throwing(safe_divide));
+        assert_eq!(flat(10.0, 2.0), Ok(5.0));
+        assert_eq!(flat(1.0, 0.0), Err("Division by zero".to_string()));
+    }
+
+    #[test]
+    fn test_uncurry3_throwing() {
+        let checked = |a: u8| move |b: u8| move |c: u8| a.checked_mul(b).and_then(|x| x.checked_add(c)).ok_or("overflow");
+        let flat = uncurry3_throwing(checked);
+        assert_eq!(flat(2, 3, 4), Ok(10));
+        assert_eq!(flat(200, 2, 0), Err("overflow"));
+    }
+}
@@ -0,0 +1,380 @@
+// Reusable validators for forms and documents (payments, sign-up forms, ...). A
+// `Validator` checks a value by reference and can run in two modes: `check` stops at the
+// first error, `validate` collects every error into a `Validated`.
+
+use core::fmt;
+
+use crate::prelude::*;
+use crate::keypath::KeyPath;
+use crate::validated::Validated;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    FailFast,
+    Accumulate,
+}
+
+type Check<T, E> = Box<dyn Fn(&T, Mode, &mut Vec<E>)>;
+
+/// A check on `T` reporting errors of type `E`.
+pub struct Validator<T: ?Sized, E>(Check<T, E>);
+
+impl<T: ?Sized + 'static, E: 'static> Validator<T, E> {
+    /// A single check; `Err` is the error it reports.
+    pub fn new(f: impl Fn(&T) -> Result<(), E> + 'static) -> Self {
+        Validator(Box::new(move |t, _, errors| {
+            if let Err(e) = f(t) {
+                errors.push(e);
+            }
+        }))
+    }
+
+    /// Fail fast: the first error, without running the remaining checks.
+    pub fn check(&self, t: &T) -> Result<(), E> {
+        let mut errors = Vec::new();
+        (self.0)(t, Mode::FailFast, &mut errors);
+        errors.into_iter().next().map_or(Ok(()), Err)
+    }
+
+    /// Accumulate: run every check and report all errors.
+    pub fn validate(&self, t: &T) -> Validated<(), E> {
+        let mut errors = Vec::new();
+        (self.0)(t, Mode::Accumulate, &mut errors);
+        if errors.is_empty() { Validated::Valid(()) } else { Validated::Invalid(errors) }
+    }
+
+    /// Both checks; in fail-fast mode `other` only runs if `self` passed.
+    pub fn and(self, other: Validator<T, E>) -> Self {
+        let (f, g) = (self.0, other.0);
+        Validator(Box::new(move |t, mode, errors| {
+            let before = errors.len();
+            f(t, mode, errors);
+            if mode == Mode::Accumulate || errors.len() == before {
+                g(t, mode, errors);
+            }
+        }))
+    }
+
+    /// Passes if either check passes; otherwise reports the errors of both.
+    pub fn or(self, other: Validator<T, E>) -> Self {
+        let (f, g) = (self.0, other.0);
+        Validator(Box::new(move |t, mode, errors| {
+            let mut first = Vec::new();
+            f(t, mode, &mut first);
+            if first.is_empty() {
+                return;
+            }
+            let mut second = Vec::new();
+            g(t, mode, &mut second);
+            if !second.is_empty() {
+                errors.extend(first);
+                errors.extend(second);
+            }
+        }))
+    }
+
+    /// Convert the reported errors, e.g. into a domain error enum.
+    pub fn map<E2: 'static>(self, f: impl Fn(E) -> E2 + 'static) -> Validator<T, E2> {
+        let g = self.0;
+        Validator(Box::new(move |t, mode, errors| {
+            let mut own = Vec::new();
+            g(t, mode, &mut own);
+            errors.extend(own.into_iter().map(&f));
+        }))
+    }
+}
+
+/// Run a validator on the field a keypath points at.
+pub fn for_field<Root: 'static, Value: 'static, E: 'static>(keypath: KeyPath<Root, Value>, validator: Validator<Value, E>) -> Validator<Root, E> {
+    let v = validator.0;
+    Validator(Box::new(move |root, mode, errors| v((keypath.get)(root), mode, errors)))
+}
+
+// --- Field paths: errors located in nested documents, e.g.
+// `payment_information[0].creditor_account.currency`. Paths are built outside-in by
+// `field` and `each` as validators are attached.
+
+/// One step into a document: a named field or a position in a list.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    Field(&'static str),
+    Index(usize),
+}
+
+/// Where in a document an error was found; empty for the document itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FieldPath(pub Vec<PathSegment>);
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) if i == 0 => write!(f, "{name}")?,
+                PathSegment::Field(name) => write!(f, ".{name}")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error together with the path of the value that caused it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError<E> {
+    pub path: FieldPath,
+    pub error: E,
+}
+
+impl<E: fmt::Display> fmt::Display for FieldError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.0.is_empty() { write!(f, "{}", self.error) } else { write!(f, "{}: {}", self.path, self.error) }
+    }
+}
+
+impl<T: ?Sized + 'static, E: 'static> Validator<T, E> {
+    /// Report errors as `FieldError`s, ready to be placed with `field` and `each`.
+    pub fn located(self) -> Validator<T, FieldError<E>> {
+        self.map(|error| FieldError { path: FieldPath::default(), error })
+    }
+}
+
+// Run `check` and put `segment` in front of the paths of the errors it added.
+fn prefixed<T: ?Sized, E>(check: &Check<T, FieldError<E>>, t: &T, mode: Mode, errors: &mut Vec<FieldError<E>>, segment: PathSegment) {
+    let before = errors.len();
+    check(t, mode, errors);
+    for e in &mut errors[before..] {
+        e.path.0.insert(0, segment.clone());
+    }
+}
+
+/// `for_field` that records `name` in the error paths.
+pub fn field<Root: 'static, Value: 'static, E: 'static>(
+    name: &'static str,
+    keypath: KeyPath<Root, Value>,
+    validator: Validator<Value, FieldError<E>>,
+) -> Validator<Root, FieldError<E>> {
+    let v = validator.0;
+    Validator(Box::new(move |root, mode, errors| prefixed(&v, (keypath.get)(root), mode, errors, PathSegment::Field(name))))
+}
+
+/// Validate every element of a list, recording its index in the error paths.
+/// In fail-fast mode the elements after the first invalid one are skipped.
+pub fn each<T: 'static, E: 'static>(validator: Validator<T, FieldError<E>>) -> Validator<Vec<T>, FieldError<E>> {
+    let v = validator.0;
+    Validator(Box::new(move |items, mode, errors| {
+        for (i, item) in items.iter().enumerate() {
+            prefixed(&v, item, mode, errors, PathSegment::Index(i));
+            if mode == Mode::FailFast && !errors.is_empty() {
+                return;
+            }
+        }
+    }))
+}
+
+/// Errors reported by the built-in validators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    Required,
+    Length { min: usize, max: usize, actual: usize },
+    Mismatch(&'static str),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::Required => write!(f, "is required"),
+            ValidationError::Length { min, max, actual } => write!(f, "length {actual} is not within {min}..={max}"),
+            ValidationError::Mismatch(expected) => write!(f, "does not match {expected}"),
+        }
+    }
+}
+
+/// Values that can be missing or blank.
+pub trait Presence {
+    fn is_present(&self) -> bool;
+}
+
+impl<T> Presence for Option<T> {
+    fn is_present(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl Presence for str {
+    fn is_present(&self) -> bool {
+        !self.trim().is_empty()
+    }
+}
+
+impl Presence for String {
+    fn is_present(&self) -> bool {
+        self.as_str().is_present()
+    }
+}
+
+impl<T> Presence for Vec<T> {
+    fn is_present(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+/// Values with a length; strings are measured in characters.
+pub trait Length {
+    fn length(&self) -> usize;
+}
+
+impl Length for str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Not `None`, not blank and not empty.
+pub fn required<T: Presence + ?Sized + 'static>() -> Validator<T, ValidationError> {
+    Validator::new(|t: &T| if t.is_present() { Ok(()) } else { Err(ValidationError::Required) })
+}
+
+/// Length within `min..=max`.
+pub fn length<T: Length + ?Sized + 'static>(min: usize, max: usize) -> Validator<T, ValidationError> {
+    Validator::new(move |t: &T| {
+        let actual = t.length();
+        if (min..=max).contains(&actual) { Ok(()) } else { Err(ValidationError::Length { min, max, actual }) }
+    })
+}
+
+/// The predicate holds; `expected` describes it in the error, e.g. `"an ISO 4217 code"`.
+pub fn matches<T: ?Sized + 'static>(expected: &'static str, predicate: impl Fn(&T) -> bool + 'static) -> Validator<T, ValidationError> {
+    Validator::new(move |t: &T| if predicate(t) { Ok(()) } else { Err(ValidationError::Mismatch(expected)) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum PaymentError {
+        Name(ValidationError),
+        Currency(ValidationError),
+    }
+
+    struct Payment {
+        creditor_name: String,
+        currency: String,
+    }
+
+    fn creditor_name() -> KeyPath<Payment, String> {
+        KeyPath::new(|p: &Payment| &p.creditor_name, |p, v| p.creditor_name = v)
+    }
+
+    fn currency() -> KeyPath<Payment, String> {
+        KeyPath::new(|p: &Payment| &p.currency, |p, v| p.currency = v)
+    }
+
+    fn is_iso_currency(s: &str) -> bool {
+        s.len() == 3 && s.chars().all(|c| c.is_ascii_uppercase())
+    }
+
+    fn payment_validator() -> Validator<Payment, PaymentError> {
+        for_field(creditor_name(), required().and(length(1, 70)).map(PaymentError::Name))
+            .and(for_field(currency(), matches("an ISO 4217 code", |s: &String| is_iso_currency(s)).map(PaymentError::Currency)))
+    }
+
+    #[test]
+    fn test_accumulate_and_fail_fast() {
+        let payment = Payment { creditor_name: " ".to_string(), currency: "usd".to_string() };
+        let v = payment_validator();
+        assert_eq!(
+            v.validate(&payment),
+            Validated::Invalid(vec![
+                PaymentError::Name(ValidationError::Required),
+                PaymentError::Currency(ValidationError::Mismatch("an ISO 4217 code")),
+            ])
+        );
+        assert_eq!(v.check(&payment), Err(PaymentError::Name(ValidationError::Required)));
+        assert!(v.validate(&Payment { creditor_name: "ACME".to_string(), currency: "EUR".to_string() }).is_valid());
+    }
+
+    #[test]
+    fn test_length_counts_chars() {
+        let v = length::<str>(2, 3);
+        assert_eq!(v.check("äöü"), Ok(()));
+        assert_eq!(v.check("abcd"), Err(ValidationError::Length { min: 2, max: 3, actual: 4 }));
+        assert_eq!(ValidationError::Length { min: 2, max: 3, actual: 4 }.to_string(), "length 4 is not within 2..=3");
+    }
+
+    #[test]
+    fn test_or() {
+        let iban_or_empty = length::<str>(0, 0).or(length(15, 34));
+        assert!(iban_or_empty.check("").is_ok());
+        assert!(iban_or_empty.check("NL91ABNA0417164300").is_ok());
+        assert_eq!(iban_or_empty.validate("NL91").into_result().unwrap_err().len(), 2);
+    }
+
+    #[test]
+    fn test_required_option() {
+        assert_eq!(required::<Option<u32>>().check(&None), Err(ValidationError::Required));
+        assert!(required::<Option<u32>>().check(&Some(0)).is_ok());
+    }
+
+    struct Account {
+        currency: String,
+    }
+
+    struct Instruction {
+        creditor_account: Account,
+    }
+
+    struct Document {
+        payment_information: Vec<Instruction>,
+    }
+
+    fn document_validator() -> Validator<Document, FieldError<ValidationError>> {
+        let currency = field(
+            "currency",
+            KeyPath::new(|a: &Account| &a.currency, |a, v| a.currency = v),
+            matches("an ISO 4217 code", |s: &String| is_iso_currency(s)).located(),
+        );
+        let instruction = field("creditor_account", KeyPath::new(|i: &Instruction| &i.creditor_account, |i, v| i.creditor_account = v), currency);
+        field(
+            "payment_information",
+            KeyPath::new(|d: &Document| &d.payment_information, |d, v| d.payment_information = v),
+            required().located().and(each(instruction)),
+        )
+    }
+
+    #[test]
+    fn test_nested_field_paths() {
+        let instruction = |currency: &str| Instruction { creditor_account: Account { currency: currency.to_string() } };
+        let doc = Document { payment_information: vec![instruction("EUR"), instruction("usd"), instruction("gbp")] };
+        let errors = document_validator().validate(&doc).into_result().unwrap_err();
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "payment_information[1].creditor_account.currency: does not match an ISO 4217 code",
+                "payment_information[2].creditor_account.currency: does not match an ISO 4217 code",
+            ]
+        );
+        assert_eq!(document_validator().check(&doc).unwrap_err().path, errors[0].path);
+        let empty = Document { payment_information: Vec::new() };
+        assert_eq!(document_validator().check(&empty).unwrap_err().to_string(), "payment_information: is required");
+    }
+}
@@ -0,0 +1,80 @@
+use crate::prelude::*;
+
+/// Like `Result`, but combining two `Invalid`s keeps the errors of both instead of
+/// stopping at the first, so a form or record can report every problem at once.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Validated<T, E> {
+    Valid(T),
+    Invalid(Vec<E>),
+}
+
+impl<T, E> Validated<T, E> {
+    /// A single error.
+    pub fn invalid(e: E) -> Self {
+        Validated::Invalid(vec![e])
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Validated::Valid(_))
+    }
+
+    pub fn map<B>(self, f: impl FnOnce(T) -> B) -> Validated<B, E> {
+        match self {
+            Validated::Valid(t) => Validated::Valid(f(t)),
+            Validated::Invalid(es) => Validated::Invalid(es),
+        }
+    }
+
+    /// Pair two values; if either is invalid, the errors of both are kept in order.
+    pub fn zip<B>(self, other: Validated<B, E>) -> Validated<(T, B), E> {
+        match (self, other) {
+            (Validated::Valid(a), Validated::Valid(b)) => Validated::Valid((a, b)),
+            (Validated::Invalid(mut es), Validated::Invalid(more)) => {
+                es.extend(more);
+                Validated::Invalid(es)
+            }
+            (Validated::Invalid(es), _) | (_, Validated::Invalid(es)) => Validated::Invalid(es),
+        }
+    }
+
+    pub fn into_result(self) -> Result<T, Vec<E>> {
+        match self {
+            Validated::Valid(t) => Ok(t),
+            Validated::Invalid(es) => Err(es),
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Validated<T, E> {
+    fn from(r: Result<T, E>) -> Self {
+        match r {
+            Ok(t) => Validated::Valid(t),
+            Err(e) => Validated::invalid(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zip_accumulates_errors() {
+        let name: Validated<&str, &str> = Validated::invalid("name is empty");
+        let age: Validated<u8, &str> = Validated::invalid("age is negative");
+        assert_eq!(name.zip(age), Validated::Invalid(vec!["name is empty", "age is negative"]));
+    }
+
+    #[test]
+    fn test_zip_valid() {
+        let v = Validated::<_, ()>::Valid(1).zip(Validated::Valid("a")).map(|(n, s)| format!("{n}{s}"));
+        assert_eq!(v.into_result(), Ok("1a".to_string()));
+    }
+
+    #[test]
+    fn test_from_result() {
+        let v: Validated<i32, &str> = Err("bad").into();
+        assert!(!v.is_valid());
+        assert_eq!(v.into_result(), Err(vec!["bad"]));
+    }
+}
@@ -0,0 +1,72 @@
+// Swift `with` / `update`: apply functions to a value in "value-first" order.
+// with(a, f) == f(a)
+// update(a, &[&f, &g]) mutates a copy of `a` by `f` then `g` and returns it.
+
+/// Left-to-right function application: like Swift `with(a, f)`
+pub fn with<A, B>(a: A, f: impl FnOnce(A) -> B) -> B {
+    f(a)
+}
+
+/// Throwing `with`: like Swift `try with(a, f)`
+pub fn with_throwing<A, B, E>(a: A, f: impl FnOnce(A) -> Result<B, E>) -> Result<B, E> {
+    f(a)
+}
+
+/// Apply a list of in-place mutations to an owned value and return it: like Swift `update(a, fs...)`
+pub fn update<A>(mut a: A, fs: &[&dyn Fn(&mut A)]) -> A {
+    update_mut(&mut a, fs);
+    a
+}
+
+/// Apply a list of mutations to a value in place: like Swift `update(&a, fs...)`
+pub fn update_mut<A>(a: &mut A, fs: &[&dyn Fn(&mut A)]) {
+    for f in fs {
+        f(a);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct User {
+        name: String,
+        age: u32,
+    }
+
+    #[test]
+    fn test_with() {
+        assert_eq!(with(3, |x| x * 2), 6);
+        assert_eq!(with("42", str::len), 2);
+    }
+
+    #[test]
+    fn test_with_throwing() {
+        assert_eq!(with_throwing("42", str::parse::<i32>), Ok(42));
+        assert!(with_throwing("x", str::parse::<i32>).is_err());
+    }
+
+    #[test]
+    fn test_update() {
+        let user = User { name: "alice".into(), age: 30 };
+        let updated = update(
+            user.clone(),
+            &[&|u: &mut User| u.age += 1, &|u: &mut User| u.name = u.name.to_uppercase()],
+        );
+        assert_eq!(updated, User { name: "ALICE".into(), age: 31 });
+        assert_eq!(user.age, 30, "original must remain unchanged");
+    }
+
+    #[test]
+    fn test_update_mut() {
+        let mut counter = 1;
+        update_mut(&mut counter, &[&|c: &mut i32| *c += 2, &|c: &mut i32| *c *= 3]);
+        assert_eq!(counter, 9);
+    }
+
+    #[test]
+    fn test_update_no_mutations() {
+        assert_eq!(update(5, &[]), 5);
+    }
+}
@@ -0,0 +1,89 @@
+// Writer-style combinators: functions return `(A, W)`, a value together with a log `W`
+// (e.g. `Vec<String>` audit entries) that is concatenated as the pipeline runs.
+
+/// A value with an empty log.
+pub fn pure<A, W: Default>(a: A) -> (A, W) {
+    (a, W::default())
+}
+
+/// Only a log entry, no meaningful value.
+pub fn tell<W>(w: W) -> ((), W) {
+    ((), w)
+}
+
+/// Transform the value, keeping the log as is.
+pub fn map<A, B, W, F>(f: F) -> impl Fn((A, W)) -> (B, W)
+where
+    F: Fn(A) -> B,
+{
+    move |(a, w): (A, W)| (f(a), w)
+}
+
+/// Feed the value into a logging step and append that step's log to the existing one.
+pub fn and_then<A, B, W, F>(f: F) -> impl Fn((A, W)) -> (B, W)
+where
+    F: Fn(A) -> (B, W),
+    W: IntoIterator + Extend<<W as IntoIterator>::Item>,
+{
+    move |(a, mut w): (A, W)| {
+        let (b, more) = f(a);
+        w.extend(more);
+        (b, w)
+    }
+}
+
+/// Pair two values, concatenating their logs in order.
+pub fn zip<A, B, W>((a, mut wa): (A, W), (b, wb): (B, W)) -> ((A, B), W)
+where
+    W: IntoIterator + Extend<<W as IntoIterator>::Item>,
+{
+    wa.extend(wb);
+    ((a, b), wa)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compose::compose3;
+
+    fn validate_amount(amount: f64) -> (f64, Vec<String>) {
+        if amount > 1000.0 {
+            (1000.0, vec![format!("amount {} capped at 1000", amount)])
+        } else {
+            (amount, vec![format!("amount {} ok", amount)])
+        }
+    }
+
+    fn apply_fee(amount: f64) -> (f64, Vec<String>) {
+        (amount + 1.5, vec!["fee 1.5 applied".to_string()])
+    }
+
+    #[test]
+    fn test_and_then_accumulates_log() {
+        let pipeline = compose3(map(|a: f64| a.round()), and_then(apply_fee), and_then(validate_amount));
+        let (amount, audit) = pipeline(pure(2000.0));
+        assert_eq!(amount, 1002.0);
+        assert_eq!(audit, vec!["amount 2000 capped at 1000".to_string(), "fee 1.5 applied".to_string()]);
+    }
+
+    #[test]
+    fn test_tell() {
+        let (unit, log) = tell(vec!["started"]);
+        assert_eq!(unit, ());
+        assert_eq!(log, vec!["started"]);
+    }
+
+    #[test]
+    fn test_zip_concatenates_logs() {
+        let ((a, b), log) = zip(validate_amount(5.0), apply_fee(10.0));
+        assert_eq!((a, b), (5.0, 11.5));
+        assert_eq!(log, vec!["amount 5 ok".to_string(), "fee 1.5 applied".to_string()]);
+    }
+
+    #[test]
+    fn test_map_keeps_log() {
+        let (len, log) = map(|s: &str| s.len())(("hello", vec![1, 2]));
+        assert_eq!(len, 5);
+        assert_eq!(log, vec![1, 2]);
+    }
+}
@@ -0,0 +1,78 @@
+// Swift `zurry` / `unzurry`: move between values and zero-argument functions.
+// zurry(f) == f()
+// unzurry(a)() == a
+
+/// Immediately call a zero-argument function.
+pub fn zurry<A>(function: impl FnOnce() -> A) -> A {
+    function()
+}
+
+/// Wrap a value in a zero-argument function that hands out a copy on every call.
+pub fn unzurry<A>(value: A) -> impl Fn() -> A
+where
+    A: Clone,
+{
+    move || value.clone()
+}
+
+// ---------------------------------------------------
+// Throwing versions (Swift `throws` → Rust `Result`)
+// ---------------------------------------------------
+
+pub fn zurry_throwing<A, E>(function: impl FnOnce() -> Result<A, E>) -> Result<A, E> {
+    function()
+}
+
+pub fn unzurry_throwing<A, E>(result: Result<A, E>) -> impl Fn() -> Result<A, E>
+where
+    A: Clone,
+    E: Clone,
+{
+    move || result.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curry::curry2;
+
+    #[test]
+    fn test_zurry() {
+        assert_eq!(zurry(|| 42), 42);
+    }
+
+    #[test]
+    fn test_zurry_curried_zero_arg() {
+        // A curried function whose last step takes no arguments.
+        let make_greeting = |name: &'static str| move || format!("Hello, {}!", name);
+        assert_eq!(zurry(make_greeting("Ann")), "Hello, Ann!");
+    }
+
+    #[test]
+    fn test_unzurry() {
+        let thunk = unzurry(vec![1, 2, 3]);
+        assert_eq!(thunk(), vec![1, 2, 3]);
+        assert_eq!(thunk(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_unzurry_roundtrip() {
+        let add = curry2(|a: i32, b: i32| a + b);
+        assert_eq!(zurry(unzurry(7)), 7);
+        assert_eq!(add(zurry(unzurry(1)))(2), 3);
+    }
+
+    #[test]
+    fn test_zurry_throwing() {
+        assert_eq!(zurry_throwing(|| "8".parse::<i32>()), Ok(8));
+        assert!(zurry_throwing(|| "x".parse::<i32>()).is_err());
+    }
+
+    #[test]
+    fn test_unzurry_throwing() {
+        let ok = unzurry_throwing::<_, String>(Ok(1));
+        let err = unzurry_throwing::<i32, _>(Err("missing".to_string()));
+        assert_eq!(ok(), Ok(1));
+        assert_eq!(err(), Err("missing".to_string()));
+    }
+}